@@ -0,0 +1,8 @@
+-brand = Bevy
+
+fluent-welcome = Welcome to { -brand }, { $name }!
+fluent-cats = { $count ->
+    [one] You have one cat
+   *[other] You have { $count } cats
+}
+    .title = Cats
@@ -0,0 +1,5 @@
+-brand = Bevy
+
+fluent-welcome = { $name }さん、{ -brand }へようこそ！
+fluent-cats = 猫を{ $count }匹飼っています
+    .title = 猫
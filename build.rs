@@ -1,20 +1,37 @@
 use std::{
+    collections::{BTreeMap, BTreeSet},
     env,
     fs::{self, File},
     io::Write,
     path::{Path, PathBuf},
 };
 
+use bevy_simple_i18n_parser::{
+    locale_file,
+    locale_keys::{
+        collect_keys, key_variants, merge_keys, plural_key, LocaleKeys, TRANSLATION_EXTENSIONS,
+    },
+    placeholders::{malformed_placeholders, placeholders},
+};
+
 const ASSET_PATH_VAR: &str = "BEVY_ASSET_PATH";
+const DEFAULT_LOCALE_VAR: &str = "BEVY_I18N_DEFAULT_LOCALE";
+const DEFAULT_LOCALE: &str = "en";
+const PLACEHOLDER_CHECK_VAR: &str = "BEVY_I18N_PLACEHOLDER_CHECK";
 const OUTPUT_FILE_NAME: &str = "bevy_simple_i18n.rs";
 const ALLOWED_EXTENSIONS: &[&str] = &["otf", "ttf"];
 
 fn main() {
     cargo_emit::rerun_if_env_changed!(ASSET_PATH_VAR);
+    cargo_emit::rerun_if_env_changed!(DEFAULT_LOCALE_VAR);
+    cargo_emit::rerun_if_env_changed!(PLACEHOLDER_CHECK_VAR);
 
     let out_dir = env::var_os("OUT_DIR").unwrap();
 
     let mut files = Vec::new();
+    let mut translation_files = Vec::new();
+    // Translations of each locale file, by asset path
+    let mut file_keys: Vec<(String, LocaleKeys)> = Vec::new();
 
     let mut marker_file = File::create(Path::new(&out_dir).join(OUTPUT_FILE_NAME)).unwrap();
 
@@ -71,18 +88,6 @@ fn main() {
         cargo_emit::rerun_if_changed!(dir.to_string_lossy());
         // cargo_emit::warning!("Asset folder found: {}", dir.to_string_lossy());
 
-        marker_file
-            .write_all(
-                format!(
-                    r#"rust_i18n::i18n!("{}");
-
-"#,
-                    dir.to_string_lossy().replace('\\', "/"),
-                )
-                .as_bytes(),
-            )
-            .unwrap();
-
         let building_for_wasm = std::env::var("CARGO_CFG_TARGET_ARCH") == Ok("wasm32".to_string());
 
         visit_dirs(&dir)
@@ -96,7 +101,14 @@ fn main() {
                 }
                 cargo_emit::rerun_if_changed!(full_path.to_string_lossy());
                 if let Some(ext) = full_path.extension().and_then(|e| e.to_str()) {
-                    if ALLOWED_EXTENSIONS.contains(&ext) {
+                    if TRANSLATION_EXTENSIONS.contains(&ext) {
+                        // Asset paths always use forward slashes
+                        let string_path = string_path.replace('\\', "/");
+                        let mut locale_keys = LocaleKeys::new();
+                        collect_keys(full_path, ext, &default_locale(), &mut locale_keys);
+                        file_keys.push((string_path.clone(), locale_keys));
+                        translation_files.push(string_path);
+                    } else if ALLOWED_EXTENSIONS.contains(&ext) {
                         // Extract filename without extension
                         let locale = path.file_stem().unwrap().to_string_lossy().into_owned();
 
@@ -132,6 +144,10 @@ fn main() {
         // panic!("No asset folder found");
     }
 
+    translation_files.sort();
+    file_keys.sort_by(|a, b| a.0.cmp(&b.0));
+    let keys = (!file_keys.is_empty()).then(|| merge_keys(file_keys.iter().map(|(_, keys)| keys)));
+
     let mut families: Vec<FontFamily> = Vec::new();
     for asset in files.iter() {
         if let Some(family) = families.iter_mut().find(|f| f.folder == asset.family) {
@@ -162,7 +178,9 @@ pub(crate) struct FontFamily {{
 }}
 
 {}
-pub(crate) const FONT_FAMILIES: &'static [FontFamily] = &[{}];
+pub(crate) const FONT_FAMILIES: &[FontFamily] = &[{}];
+
+pub(crate) const TRANSLATION_FILES: &[&str] = &{:?};
 "#,
                 families
                     .iter()
@@ -173,11 +191,23 @@ pub(crate) const FONT_FAMILIES: &'static [FontFamily] = &[{}];
                     .iter()
                     .map(|s| s.push_const())
                     .collect::<Vec<_>>()
-                    .join(", ")
+                    .join(", "),
+                translation_files,
             )
             .as_bytes(),
         )
         .unwrap();
+    if let Some(keys) = &keys {
+        check_placeholders(keys, &file_keys);
+    }
+    marker_file
+        .write_all(key_macro(keys.as_ref()).as_bytes())
+        .unwrap();
+    if env::var_os("CARGO_FEATURE_TYPED_KEYS").is_some() {
+        marker_file
+            .write_all(typed_keys(keys.as_ref()).as_bytes())
+            .unwrap();
+    }
 }
 
 struct FontAsset {
@@ -211,7 +241,7 @@ impl FontFamily {
     }
 
     fn push_const(&self) -> String {
-        format!("{}", self.snake_case().to_uppercase())
+        self.snake_case().to_uppercase()
     }
 
     fn snake_case(&self) -> String {
@@ -244,3 +274,388 @@ fn visit_dirs(dir: &Path) -> Vec<PathBuf> {
     }
     collected
 }
+
+/// Returns the default locale the keys are checked against
+fn default_locale() -> String {
+    env::var(DEFAULT_LOCALE_VAR).unwrap_or_else(|_| DEFAULT_LOCALE.to_string())
+}
+
+/// Returns the keys that can be translated, e.g. `messages.cats` for the plural forms `messages.cats.one`
+/// and `dialogue.found` for the plural forms of its select variants `dialogue.found.female.one`
+fn translatable_keys(keys: &BTreeMap<String, String>) -> BTreeSet<String> {
+    // Select keys have an `other` variant, `dialogue.found.other` or its plural forms `dialogue.found.other.one`
+    let is_select_key = |key: &str| {
+        let other = format!("{}.other", key);
+        keys.keys().any(|key| {
+            key.strip_prefix(other.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        })
+    };
+    let mut translatable: BTreeSet<String> = keys.keys().cloned().collect();
+    for key in keys.keys() {
+        let Some((parent, category)) = key.rsplit_once('.') else {
+            continue;
+        };
+        if !locale_file::PLURAL_CATEGORIES.contains(&category) {
+            continue;
+        }
+        translatable.insert(parent.to_string());
+        if let Some((grandparent, _)) = parent
+            .rsplit_once('.')
+            .filter(|(grandparent, _)| is_select_key(grandparent))
+        {
+            translatable.insert(grandparent.to_string());
+        }
+    }
+    translatable
+}
+
+/// Writes the `i18n_key!` macro, it returns the known keys of the default locale as is
+/// and lets the proc macro report the others with the close matches
+fn key_macro(keys: Option<&LocaleKeys>) -> String {
+    const DOCS: &str = r#"/// Checks at compile time that the translation key exists in the locale files of the asset folder
+///
+/// Unknown keys and keys missing from the default locale, `en` unless set with `$BEVY_I18N_DEFAULT_LOCALE`,
+/// fail the compilation with the closest known keys. Keys of plural and select variants are checked
+/// without their suffix, e.g. `messages.cats` for `messages.cats.one`
+///
+/// # Example
+/// ```ignore
+/// use bevy_simple_i18n::{i18n_key, prelude::*};
+///
+/// commands.spawn(I18nText::new(i18n_key!("messages.hello")));
+/// ```
+"#;
+
+    let Some(keys) = keys.filter(|keys| !keys.is_empty()) else {
+        // No locale files to check the keys against, e.g. on docs.rs
+        return format!(
+            "\n{}#[macro_export]\nmacro_rules! i18n_key {{\n    ($key:literal) => {{\n        $key\n    }};\n}}\n",
+            DOCS
+        );
+    };
+
+    let default_locale = default_locale();
+    let translatable: BTreeMap<&str, BTreeSet<String>> = keys
+        .iter()
+        .map(|(locale, keys)| (locale.as_str(), translatable_keys(keys)))
+        .collect();
+    let default_keys = translatable
+        .get(default_locale.as_str())
+        .cloned()
+        .unwrap_or_default();
+    if default_keys.is_empty() {
+        cargo_emit::warning!(
+            "No translations found for the default locale `{}`, set it with ${}",
+            default_locale,
+            DEFAULT_LOCALE_VAR
+        );
+    }
+
+    let mut other_keys: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (locale, keys) in &translatable {
+        for key in keys.iter().filter(|key| !default_keys.contains(*key)) {
+            other_keys.entry(key).or_default().push(locale);
+        }
+    }
+
+    let mut arms = String::new();
+    for key in &default_keys {
+        arms.push_str(&format!(
+            "    ({:?}) => {{\n        {:?}\n    }};\n",
+            key, key
+        ));
+    }
+    format!(
+        r#"
+{}#[macro_export]
+macro_rules! i18n_key {{
+{}    ($key:literal) => {{
+        $crate::__i18n_key_error!($key, {:?}, [{}], [{}])
+    }};
+}}
+"#,
+        DOCS,
+        arms,
+        default_locale,
+        default_keys
+            .iter()
+            .map(|key| format!("{:?}", key))
+            .collect::<Vec<_>>()
+            .join(", "),
+        other_keys
+            .iter()
+            .map(|(key, locales)| format!("({:?}, {:?})", key, locales.join(", ")))
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+/// Key of the generated `Keys` enum
+#[derive(Default)]
+struct TypedKey {
+    variant: String,
+    /// Translation of the default locale, shown in the docs of the variant
+    text: String,
+    placeholders: BTreeSet<String>,
+}
+
+/// Writes the `keys` module, with a `Keys` variant for each key of the default locale,
+/// plural forms are merged into their key and the placeholders become the fields of the variant
+fn typed_keys(keys: Option<&LocaleKeys>) -> String {
+    let default_keys = keys
+        .and_then(|keys| keys.get(&default_locale()))
+        .cloned()
+        .unwrap_or_default();
+
+    let mut typed: BTreeMap<&str, TypedKey> = BTreeMap::new();
+    for (key, text) in &default_keys {
+        let typed_key = typed.entry(plural_key(key)).or_default();
+        // The docs show the `other` form of plural keys
+        if typed_key.text.is_empty() || key.ends_with(".other") {
+            typed_key.text = text.clone();
+        }
+        typed_key.placeholders.extend(placeholders(text));
+    }
+
+    for (key, variant) in key_variants(default_keys.keys().map(String::as_str)) {
+        if let Some(typed_key) = typed.get_mut(key) {
+            typed_key.variant = variant;
+        }
+    }
+
+    let mut enum_variants = String::new();
+    let mut key_arms = String::new();
+    let mut args_arms = String::new();
+    for (key, typed_key) in &typed {
+        let fields = typed_key
+            .placeholders
+            .iter()
+            .map(|name| (name, field_name(name)))
+            .collect::<Vec<_>>();
+        let pattern = if fields.is_empty() {
+            String::new()
+        } else {
+            let names = fields
+                .iter()
+                .map(|(_, field)| field.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(" {{ {} }}", names)
+        };
+
+        enum_variants.push_str(&format!(
+            "        #[doc = {:?}]\n        {}",
+            format!("`{}`: {}", key, typed_key.text),
+            typed_key.variant
+        ));
+        if !fields.is_empty() {
+            enum_variants.push_str(" {\n");
+            for (name, field) in &fields {
+                let ty = if name.as_str() == "count" {
+                    "f64"
+                } else {
+                    "String"
+                };
+                enum_variants.push_str(&format!("            {}: {},\n", field, ty));
+            }
+            enum_variants.push_str("        }");
+        }
+        enum_variants.push_str(",\n");
+
+        key_arms.push_str(&format!(
+            "                Self::{}{} => {:?},\n",
+            typed_key.variant,
+            if fields.is_empty() { "" } else { " { .. }" },
+            key
+        ));
+        args_arms.push_str(&format!(
+            "                Self::{}{} => vec![{}],\n",
+            typed_key.variant,
+            pattern,
+            fields
+                .iter()
+                .map(|(name, field)| {
+                    let arg = if name.as_str() == "count" {
+                        format!("number_arg({})", field)
+                    } else {
+                        format!("InterpolationType::String({})", field)
+                    };
+                    format!("({:?}.to_string(), {})", name, arg)
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    // Only written when a key has a `count`, so it is never unused
+    const NUMBER_ARG: &str = r#"
+    #[cfg(feature = "numbers")]
+    fn number_arg(value: f64) -> InterpolationType {
+        InterpolationType::Number(crate::components::I18nNumber::new(value))
+    }
+
+    #[cfg(not(feature = "numbers"))]
+    fn number_arg(value: f64) -> InterpolationType {
+        InterpolationType::String(value.to_string())
+    }
+"#;
+    let has_numbers = typed
+        .values()
+        .any(|typed_key| typed_key.placeholders.contains("count"));
+
+    let (key_body, args_body) = if typed.is_empty() {
+        ("match *self {}".to_string(), "match self {}".to_string())
+    } else {
+        (
+            format!("match self {{\n{}            }}", key_arms),
+            format!("match self {{\n{}            }}", args_arms),
+        )
+    };
+
+    format!(
+        r#"
+/// Translation keys generated from the locale files of the asset folder, with the `typed_keys` feature
+pub mod keys {{
+    use crate::components::InterpolationType;
+
+    /// Translation keys of the default locale, the `%{{placeholders}}` of a key are the fields of its variant
+    ///
+    /// `count` placeholders are numbers, so they select the plural form of the translation
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Keys {{
+{}    }}
+
+    impl Keys {{
+        /// Returns the translation key
+        pub fn key(&self) -> &'static str {{
+            {}
+        }}
+
+        /// Returns the interpolation arguments of the key
+        pub(crate) fn into_args(self) -> Vec<(String, InterpolationType)> {{
+            {}
+        }}
+    }}
+{}}}
+"#,
+        enum_variants,
+        key_body,
+        args_body,
+        if has_numbers { NUMBER_ARG } else { "" }
+    )
+}
+
+/// Converts a placeholder to the name of its field, `first-name` -> `first_name`
+fn field_name(placeholder: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern",
+        "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+        "pub", "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use",
+        "where", "while", "abstract", "become", "box", "do", "final", "gen", "macro", "override",
+        "priv", "try", "typeof", "unsized", "virtual", "yield",
+    ];
+    let mut name: String = placeholder
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.starts_with(|c: char| c.is_numeric()) {
+        name.insert(0, '_');
+    }
+    match name.as_str() {
+        "self" | "Self" | "super" | "crate" | "_" => format!("{}_", name),
+        name if KEYWORDS.contains(&name) => format!("r#{}", name),
+        _ => name,
+    }
+}
+
+/// Compares the placeholders of every translation with the ones of the default locale,
+/// reported as warnings, errors with `$BEVY_I18N_PLACEHOLDER_CHECK=deny`, or skipped with `allow`
+fn check_placeholders(keys: &LocaleKeys, file_keys: &[(String, LocaleKeys)]) {
+    let check = env::var(PLACEHOLDER_CHECK_VAR).unwrap_or_else(|_| "warn".to_string());
+    match check.as_str() {
+        "allow" => return,
+        "warn" | "deny" => {}
+        _ => cargo_emit::warning!(
+            "Unknown ${} value `{}`, expected `warn`, `deny` or `allow`",
+            PLACEHOLDER_CHECK_VAR,
+            check
+        ),
+    }
+
+    // Placeholders of each key, the plural forms of a key are merged as they don't all need the count
+    let placeholders_by_key = |translations: &BTreeMap<String, String>| {
+        let mut by_key: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for (key, text) in translations {
+            by_key
+                .entry(plural_key(key).to_string())
+                .or_default()
+                .extend(placeholders(text));
+        }
+        by_key
+    };
+
+    let default_locale = default_locale();
+    let default_placeholders = keys
+        .get(&default_locale)
+        .map(placeholders_by_key)
+        .unwrap_or_default();
+
+    let mut problems = Vec::new();
+    for (path, locale, translations) in file_keys.iter().flat_map(|(path, keys)| {
+        keys.iter()
+            .map(move |(locale, translations)| (path, locale, translations))
+    }) {
+        for (key, text) in translations {
+            for placeholder in malformed_placeholders(text) {
+                problems.push(format!(
+                    "{}: `{}` translation of `{}` has a malformed placeholder `{}`",
+                    path, locale, key, placeholder
+                ));
+            }
+        }
+        if *locale == default_locale {
+            continue;
+        }
+
+        for (key, placeholders) in placeholders_by_key(translations) {
+            let Some(expected) = default_placeholders.get(&key) else {
+                continue;
+            };
+            let missing = expected.difference(&placeholders).collect::<Vec<_>>();
+            let extra = placeholders.difference(expected).collect::<Vec<_>>();
+            match (missing.as_slice(), extra.as_slice()) {
+                ([], []) => {}
+                ([missing], [extra]) => problems.push(format!(
+                    "{}: `{}` translation of `{}` renamed the placeholder `{}` to `{}`",
+                    path, locale, key, missing, extra
+                )),
+                _ => {
+                    for missing in missing {
+                        problems.push(format!(
+                            "{}: `{}` translation of `{}` is missing the placeholder `{}`",
+                            path, locale, key, missing
+                        ));
+                    }
+                    for extra in extra {
+                        problems.push(format!(
+                            "{}: `{}` translation of `{}` has the unknown placeholder `{}`",
+                            path, locale, key, extra
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    if check == "deny" && !problems.is_empty() {
+        panic!(
+            "Inconsistent placeholders in the locale files, compared with the default locale `{}`:\n{}",
+            default_locale,
+            problems.join("\n")
+        );
+    }
+    for problem in problems {
+        cargo_emit::warning!("{}", problem);
+    }
+}
@@ -0,0 +1,417 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use bevy_simple_i18n_parser::locale_file::{flatten_keys, parse_v2};
+use serde_json::{Map, Value};
+
+use crate::{
+    default_locale,
+    sources::{self, Reference},
+};
+
+const USAGE: &str = "Usage: bevy_simple_i18n extract [OPTIONS]
+
+Adds the keys of the Rust sources missing from a template locale file, with their placeholders
+
+Comments of YAML templates are not kept when keys are added
+
+Options:
+  --assets <DIR>     Asset folder with the locale files [default: assets]
+  --src <DIR>        Folder of the Rust sources, can be repeated [default: src]
+  --output <FILE>    Template locale file, JSON or YAML, created if missing
+                     [default: the JSON or YAML file of the locale in the asset folder, or locales/{locale}.json]
+  --locale <LOCALE>  Locale of the template, used by `_version: 2` files [default: $BEVY_I18N_DEFAULT_LOCALE or en]
+  -h, --help         Print this help";
+
+struct Args {
+    assets: PathBuf,
+    src: Vec<PathBuf>,
+    output: Option<PathBuf>,
+    locale: String,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut parsed = Self {
+            assets: PathBuf::from("assets"),
+            src: vec![],
+            output: None,
+            locale: default_locale(),
+        };
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+            match arg.as_str() {
+                "--assets" => parsed.assets = PathBuf::from(value()?),
+                "--src" => parsed.src.push(PathBuf::from(value()?)),
+                "--output" => parsed.output = Some(PathBuf::from(value()?)),
+                "--locale" => parsed.locale = value()?,
+                "-h" | "--help" => return Ok(None),
+                _ => return Err(format!("Unknown argument: {}", arg)),
+            }
+        }
+        if parsed.src.is_empty() {
+            parsed.src.push(PathBuf::from("src"));
+        }
+        Ok(Some(parsed))
+    }
+}
+
+/// Extracts the keys of the Rust sources into the template locale file
+pub(crate) fn run(args: impl Iterator<Item = String>) -> ExitCode {
+    let args = match Args::parse(args) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let output = args
+        .output
+        .unwrap_or_else(|| default_template(&args.assets, &args.locale));
+
+    // Variants of the `Keys` enum are keys of the locale files already
+    let keys = sources::referenced_keys(&args.src, &BTreeMap::new());
+    match update_template(&output, &args.locale, &keys) {
+        Ok(added) if added.is_empty() => {
+            println!("{} is up to date ({} keys)", output.display(), keys.len());
+            ExitCode::SUCCESS
+        }
+        Ok(added) => {
+            println!("Added {} keys to {}:", added.len(), output.display());
+            for key in added {
+                println!("  {}", key);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Returns the template of the locale, its first `_version: 1` JSON or YAML file in the asset folder,
+/// e.g. `assets/locales/en.yml`, or `assets/locales/{locale}.json` if it has none
+fn default_template(assets: &Path, locale: &str) -> PathBuf {
+    crate::locale_files(assets)
+        .into_iter()
+        .find(|path| {
+            let is_template = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| matches!(ext, "json" | "yml" | "yaml"));
+            // `app.en.json` -> `en`
+            let stem_locale = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.split('.').next_back());
+            is_template && stem_locale == Some(locale)
+        })
+        .unwrap_or_else(|| assets.join("locales").join(format!("{}.json", locale)))
+}
+
+/// Adds the keys of the sources missing from the template locale file, existing translations are kept as is
+///
+/// New keys are translated with their placeholders, e.g. `%{name}`, so translators know which arguments they get,
+/// and are nested like the existing keys of `_version: 1` templates, `menu.quit` goes into `menu: { start: .. }`.
+/// Keys with a context are `msgid`s of gettext catalogs and are left out. Returns the added keys
+///
+/// The template is only written when keys are added, YAML templates lose their comments then
+fn update_template(
+    path: &Path,
+    locale: &str,
+    keys: &BTreeMap<String, Reference>,
+) -> Result<Vec<String>, String> {
+    let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default();
+    if !matches!(ext, "json" | "yml" | "yaml") {
+        return Err(format!(
+            "Unsupported template extension `{}`, expected json, yml or yaml",
+            ext
+        ));
+    }
+
+    let content = if path.exists() {
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+        Some(content)
+    } else {
+        None
+    };
+    let mut template = match &content {
+        Some(content) => {
+            let value = if ext == "json" {
+                serde_json::from_str(content).map_err(|err| err.to_string())
+            } else {
+                serde_yaml::from_str(content).map_err(|err| err.to_string())
+            };
+            value.map_err(|err| format!("Invalid template {}: {}", path.display(), err))?
+        }
+        None => serde_json::json!({ "_version": 1 }),
+    };
+    let is_v2 = template.get("_version").and_then(Value::as_u64) == Some(2);
+
+    let mut existing = BTreeSet::new();
+    if is_v2 {
+        parse_v2(&template, &mut |key_locale, key, _| {
+            if key_locale == locale {
+                existing.insert(key);
+            }
+        });
+    } else {
+        flatten_keys("", &template, &mut |key, _| {
+            existing.insert(key);
+        });
+    }
+    // Plural forms and select variants are translations of their key, `messages.cats.one`
+    let exists = |key: &str| {
+        existing.iter().any(|existing| {
+            existing == key
+                || existing
+                    .strip_prefix(key)
+                    .is_some_and(|rest| rest.starts_with('.'))
+        })
+    };
+
+    let Value::Object(messages) = &mut template else {
+        return Err(format!("Invalid template {}: not a map", path.display()));
+    };
+    // New groups of keys are nested as well if the template nests its keys
+    let is_nested = messages.values().any(Value::is_object);
+    let mut added = vec![];
+    let new_keys = keys
+        .iter()
+        .filter(|(key, reference)| reference.contexts.is_empty() && !exists(key));
+    for (key, reference) in new_keys {
+        let text = Value::String(
+            reference
+                .args
+                .iter()
+                .map(|arg| format!("%{{{}}}", arg))
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+        if is_v2 {
+            match messages.get_mut(key) {
+                Some(Value::Object(locales)) => {
+                    locales.insert(locale.to_string(), text);
+                }
+                _ => {
+                    let mut locales = Map::new();
+                    locales.insert(locale.to_string(), text);
+                    messages.insert(key.clone(), Value::Object(locales));
+                }
+            }
+        } else {
+            insert_nested(messages, key, text, is_nested);
+        }
+        added.push(key.clone());
+    }
+    if added.is_empty() && content.is_some() {
+        return Ok(added);
+    }
+    let has_comments = content.as_deref().is_some_and(|content| {
+        ext != "json"
+            && content
+                .lines()
+                .any(|line| line.trim_start().starts_with('#'))
+    });
+    if has_comments {
+        eprintln!(
+            "The comments of {} are not kept, serde_yaml doesn't read them",
+            path.display()
+        );
+    }
+
+    let content = if ext == "json" {
+        serde_json::to_string_pretty(&template)
+            .map(|json| json + "\n")
+            .map_err(|err| err.to_string())
+    } else {
+        serde_yaml::to_string(&template).map_err(|err| err.to_string())
+    }?;
+    let write = |path: &Path| {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)
+    };
+    write(path).map_err(|err| format!("Could not write {}: {}", path.display(), err))?;
+    Ok(added)
+}
+
+/// Inserts the translation of a new key of a `_version: 1` template into the deepest object it belongs to,
+/// `menu.pause.quit` goes into `menu: { pause: { .. } }` or into `menu.pause: { .. }`
+///
+/// The rest of the key is nested into new objects if `nest` is true, `menu.quit` -> `menu: { quit: .. }`
+fn insert_nested(messages: &mut Map<String, Value>, key: &str, text: Value, nest: bool) {
+    let segments = key.split('.').collect::<Vec<_>>();
+    let parent = (1..segments.len())
+        .rev()
+        .map(|end| segments[..end].join("."))
+        .find(|parent| messages.get(parent).is_some_and(Value::is_object));
+    if let Some(parent) = parent {
+        if let Some(Value::Object(nested)) = messages.get_mut(&parent) {
+            insert_nested(nested, &key[parent.len() + 1..], text, nest);
+        }
+        return;
+    }
+    match key.split_once('.') {
+        Some((group, rest)) if nest && !messages.contains_key(group) => {
+            let mut nested = Map::new();
+            insert_nested(&mut nested, rest, text, nest);
+            messages.insert(group.to_string(), Value::Object(nested));
+        }
+        _ => {
+            messages.insert(key.to_string(), text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes the template into a new temporary folder and returns its path
+    fn template(name: &str, content: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "bevy_simple_i18n_extract_{}_{}",
+            std::process::id(),
+            name.replace('.', "_")
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn keys(keys: &[(&str, &[&str])]) -> BTreeMap<String, Reference> {
+        keys.iter()
+            .map(|(key, args)| {
+                let reference = Reference {
+                    args: args.iter().map(|arg| arg.to_string()).collect(),
+                    ..Default::default()
+                };
+                (key.to_string(), reference)
+            })
+            .collect()
+    }
+
+    fn read_json(path: &Path) -> Value {
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn nested_json_templates_keep_their_nesting() {
+        let path = template(
+            "en.json",
+            r#"{ "_version": 1, "menu": { "start": "Start" }, "messages.cats": { "one": "%{count} cat" } }"#,
+        );
+        let added = update_template(
+            &path,
+            "en",
+            &keys(&[
+                ("menu.start", &[]),
+                ("menu.quit", &[]),
+                ("messages.cats", &["count"]),
+                ("messages.cats.title", &[]),
+                ("hud.score", &["count", "player"]),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(added, ["hud.score", "menu.quit", "messages.cats.title"]);
+        assert_eq!(
+            read_json(&path),
+            serde_json::json!({
+                "_version": 1,
+                "menu": { "start": "Start", "quit": "" },
+                "messages.cats": { "one": "%{count} cat", "title": "" },
+                "hud": { "score": "%{count} %{player}" },
+            })
+        );
+        // Running it again keeps the template as is
+        assert!(update_template(&path, "en", &keys(&[("menu.quit", &[])]))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn flat_json_templates_stay_flat() {
+        let path = template(
+            "en.json",
+            r#"{ "_version": 1, "messages.hello": "Hello %{name}" }"#,
+        );
+        update_template(&path, "en", &keys(&[("messages.bye", &["name"])])).unwrap();
+        assert_eq!(
+            read_json(&path),
+            serde_json::json!({
+                "_version": 1,
+                "messages.hello": "Hello %{name}",
+                "messages.bye": "%{name}",
+            })
+        );
+    }
+
+    #[test]
+    fn yaml_v2_templates() {
+        let path = template(
+            "app.yml",
+            "_version: 2\nhello:\n  en: Hello\n  fr: Bonjour\n",
+        );
+        let added =
+            update_template(&path, "en", &keys(&[("hello", &[]), ("bye", &["name"])])).unwrap();
+        assert_eq!(added, ["bye"]);
+
+        let mut translations = BTreeMap::new();
+        let value: Value = serde_yaml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        parse_v2(&value, &mut |locale, key, text| {
+            translations.insert((locale.to_string(), key), text);
+        });
+        assert_eq!(
+            translations,
+            BTreeMap::from([
+                (("en".to_string(), "bye".to_string()), "%{name}".to_string()),
+                (("en".to_string(), "hello".to_string()), "Hello".to_string()),
+                (
+                    ("fr".to_string(), "hello".to_string()),
+                    "Bonjour".to_string()
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn up_to_date_templates_are_not_rewritten() {
+        let content = "# Menu\nmenu:\n  start: Start # Button\n";
+        let path = template("en.yaml", content);
+        let added = update_template(&path, "en", &keys(&[("menu.start", &[])])).unwrap();
+        assert!(added.is_empty());
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
+    }
+
+    #[test]
+    fn default_template_of_the_locale() {
+        let path = template("en.yml", "hello: Hello\n");
+        let assets = path.parent().unwrap();
+        assert_eq!(default_template(assets, "en"), path);
+        assert_eq!(
+            default_template(assets, "fr"),
+            assets.join("locales").join("fr.json")
+        );
+    }
+}
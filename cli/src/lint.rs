@@ -0,0 +1,115 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use crate::{default_locale, report::Report, sources};
+
+const USAGE: &str = "Usage: bevy_simple_i18n [lint] [OPTIONS]
+       bevy_simple_i18n extract [OPTIONS]
+
+Reports the coverage of each locale, the missing and untranslated keys, and the unused keys
+
+Options:
+  --assets <DIR>             Asset folder with the locale files [default: assets]
+  --src <DIR>                Folder of the Rust sources, can be repeated [default: src]
+  --default-locale <LOCALE>  Locale the others are compared with [default: $BEVY_I18N_DEFAULT_LOCALE or en]
+  --json                     Print the report as JSON
+  --min-coverage <PERCENT>   Fail if the coverage of a locale is lower
+  -h, --help                 Print this help";
+
+struct Args {
+    assets: PathBuf,
+    src: Vec<PathBuf>,
+    default_locale: String,
+    json: bool,
+    min_coverage: Option<f64>,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut parsed = Self {
+            assets: PathBuf::from("assets"),
+            src: vec![],
+            default_locale: default_locale(),
+            json: false,
+            min_coverage: None,
+        };
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+            match arg.as_str() {
+                "--assets" => parsed.assets = PathBuf::from(value()?),
+                "--src" => parsed.src.push(PathBuf::from(value()?)),
+                "--default-locale" => parsed.default_locale = value()?,
+                "--json" => parsed.json = true,
+                "--min-coverage" => {
+                    let coverage = value()?;
+                    let coverage = coverage
+                        .trim_end_matches('%')
+                        .parse()
+                        .map_err(|_| format!("Invalid coverage: {}", coverage))?;
+                    parsed.min_coverage = Some(coverage);
+                }
+                "-h" | "--help" => return Ok(None),
+                _ => return Err(format!("Unknown argument: {}", arg)),
+            }
+        }
+        if parsed.src.is_empty() {
+            parsed.src.push(PathBuf::from("src"));
+        }
+        Ok(Some(parsed))
+    }
+}
+
+/// Lints the translations of the project
+pub(crate) fn run(args: impl Iterator<Item = String>) -> ExitCode {
+    let args = match Args::parse(args) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    if !args.assets.is_dir() {
+        eprintln!("Could not find the asset folder: {}", args.assets.display());
+        return ExitCode::from(2);
+    }
+
+    let keys = crate::locale_keys(&args.assets, &args.default_locale);
+    if !keys.contains_key(&args.default_locale) {
+        eprintln!(
+            "No translations found for the default locale `{}`, set it with --default-locale",
+            args.default_locale
+        );
+        return ExitCode::from(2);
+    }
+
+    let typed_keys = sources::typed_keys(&keys[&args.default_locale]);
+    let report = Report::new(
+        &keys,
+        &args.default_locale,
+        &sources::referenced_keys(&args.src, &typed_keys),
+    );
+    if args.json {
+        println!("{:#}", report.to_json());
+    } else {
+        print!("{}", report.to_text());
+    }
+
+    let Some(min_coverage) = args.min_coverage else {
+        return ExitCode::SUCCESS;
+    };
+    let below = report
+        .locales
+        .iter()
+        .filter(|locale| locale.coverage() < min_coverage)
+        .map(|locale| format!("{} ({:.1}%)", locale.locale, locale.coverage()))
+        .collect::<Vec<_>>();
+    if below.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("Coverage below {}%: {}", min_coverage, below.join(", "));
+        ExitCode::FAILURE
+    }
+}
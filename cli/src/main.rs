@@ -0,0 +1,69 @@
+//! Lints the translations of a `bevy_simple_i18n` project: the coverage of each locale,
+//! the keys missing or left untranslated, and the keys never referenced by the Rust sources,
+//! and extracts the keys of the Rust sources into a template locale file
+
+mod extract;
+mod lint;
+mod report;
+mod sources;
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use bevy_simple_i18n_parser::locale_keys::{
+    collect_keys, merge_keys, LocaleKeys, TRANSLATION_EXTENSIONS,
+};
+
+const DEFAULT_LOCALE_VAR: &str = "BEVY_I18N_DEFAULT_LOCALE";
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1).peekable();
+    match args.peek().map(String::as_str) {
+        Some("extract") => extract::run(args.skip(1)),
+        Some("lint") => lint::run(args.skip(1)),
+        _ => lint::run(args),
+    }
+}
+
+/// Returns the default locale, `$BEVY_I18N_DEFAULT_LOCALE` or `en` like in the build script
+fn default_locale() -> String {
+    env::var(DEFAULT_LOCALE_VAR).unwrap_or_else(|_| "en".to_string())
+}
+
+/// Returns the translations of the locale files of the asset folder
+fn locale_keys(assets: &Path, default_locale: &str) -> LocaleKeys {
+    let file_keys = locale_files(assets)
+        .into_iter()
+        .filter_map(|path| {
+            let ext = path.extension()?.to_str()?;
+            let mut keys = LocaleKeys::new();
+            collect_keys(&path, ext, default_locale, &mut keys);
+            Some(keys)
+        })
+        .collect::<Vec<_>>();
+    merge_keys(&file_keys)
+}
+
+/// Returns the locale files of the asset folder, sorted so the first file with a key wins like in the build script
+fn locale_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = vec![];
+    let Ok(entries) = fs::read_dir(dir) else {
+        return files;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            files.append(&mut locale_files(&path));
+        } else if path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| TRANSLATION_EXTENSIONS.contains(&ext))
+        {
+            files.push(path);
+        }
+    }
+    files.sort_by(|a, b| a.to_string_lossy().cmp(&b.to_string_lossy()));
+    files
+}
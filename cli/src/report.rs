@@ -0,0 +1,237 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use bevy_simple_i18n_parser::locale_keys::{plural_key, LocaleKeys};
+use serde_json::{json, Value};
+
+use crate::sources::Reference;
+
+/// Keys translated by the components of `bevy_simple_i18n` rather than referenced in code,
+/// e.g. `ordinal.few` or `currency.USD.other`
+const LIBRARY_KEYS: &[&str] = &["ordinal", "currency", "unit"];
+
+/// Translation status of a locale compared with the default locale
+pub(crate) struct LocaleReport {
+    pub locale: String,
+    pub translated: usize,
+    pub total: usize,
+    /// Keys of the default locale without a translation
+    pub missing: Vec<String>,
+    /// Translations identical to the default locale, likely left untranslated
+    pub untranslated: Vec<String>,
+}
+
+impl LocaleReport {
+    /// Returns the percentage of the keys of the default locale that are translated
+    pub fn coverage(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.translated as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+/// Translation status of the project
+pub(crate) struct Report {
+    pub default_locale: String,
+    /// Keys of the default locale, plural forms and select variants are counted once
+    pub keys: usize,
+    pub locales: Vec<LocaleReport>,
+    /// Keys of the default locale that are never referenced by the Rust sources
+    pub unused: Vec<String>,
+}
+
+impl Report {
+    pub fn new(
+        keys: &LocaleKeys,
+        default_locale: &str,
+        referenced: &BTreeMap<String, Reference>,
+    ) -> Self {
+        let empty = BTreeMap::new();
+        let default_translations = keys.get(default_locale).unwrap_or(&empty);
+        let default_keys = default_translations
+            .keys()
+            .map(|key| base_key(key, referenced))
+            .collect::<BTreeSet<_>>();
+
+        let locales = keys
+            .iter()
+            .filter(|(locale, _)| *locale != default_locale)
+            .map(|(locale, translations)| {
+                let translated_keys = translations
+                    .keys()
+                    .map(|key| base_key(key, referenced))
+                    .collect::<BTreeSet<_>>();
+                let missing = default_keys
+                    .difference(&translated_keys)
+                    .map(|key| key.to_string())
+                    .collect::<Vec<_>>();
+                let untranslated = translations
+                    .iter()
+                    .filter(|(key, text)| {
+                        has_words(text) && default_translations.get(*key) == Some(*text)
+                    })
+                    .map(|(key, _)| key.clone())
+                    .collect();
+                LocaleReport {
+                    locale: locale.clone(),
+                    translated: default_keys.len() - missing.len(),
+                    total: default_keys.len(),
+                    missing,
+                    untranslated,
+                }
+            })
+            .collect();
+
+        let unused = default_keys
+            .iter()
+            .filter(|key| {
+                let root = key.split('.').next().unwrap_or_default();
+                !LIBRARY_KEYS.contains(&root) && !referenced.contains_key(**key)
+            })
+            .map(|key| key.to_string())
+            .collect();
+
+        Self {
+            default_locale: default_locale.to_string(),
+            keys: default_keys.len(),
+            locales,
+            unused,
+        }
+    }
+
+    /// Returns the report as JSON, for CI pipelines
+    pub fn to_json(&self) -> Value {
+        json!({
+            "default_locale": self.default_locale,
+            "keys": self.keys,
+            "locales": self.locales.iter().map(|locale| json!({
+                "locale": locale.locale,
+                "coverage": (locale.coverage() * 100.0).round() / 100.0,
+                "translated": locale.translated,
+                "total": locale.total,
+                "missing": locale.missing,
+                "untranslated": locale.untranslated,
+            })).collect::<Vec<_>>(),
+            "unused": self.unused,
+        })
+    }
+
+    /// Returns the report as text, for humans
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "Default locale: {} ({} keys)\n",
+            self.default_locale, self.keys
+        );
+        for locale in &self.locales {
+            text.push_str(&format!(
+                "\n{}: {:.1}% ({}/{})\n",
+                locale.locale,
+                locale.coverage(),
+                locale.translated,
+                locale.total
+            ));
+            list(&mut text, "Missing", &locale.missing);
+            list(&mut text, "Untranslated", &locale.untranslated);
+        }
+        if !self.unused.is_empty() {
+            text.push('\n');
+            list(&mut text, "Unused", &self.unused);
+        }
+        text
+    }
+}
+
+/// Returns the key without its plural category, or the referenced key it is a select variant of,
+/// `dialogue.found` for `dialogue.found.female` and `dialogue.found.other`
+fn base_key<'a>(key: &'a str, referenced: &'a BTreeMap<String, Reference>) -> &'a str {
+    referenced
+        .keys()
+        .filter(|referenced| {
+            key.strip_prefix(referenced.as_str())
+                .is_some_and(|rest| rest.starts_with('.'))
+        })
+        .max_by_key(|referenced| referenced.len())
+        .map_or_else(|| plural_key(key), String::as_str)
+}
+
+fn list(text: &mut String, title: &str, keys: &[String]) {
+    if keys.is_empty() {
+        return;
+    }
+    text.push_str(&format!("  {} ({}):\n", title, keys.len()));
+    for key in keys {
+        text.push_str(&format!("    {}\n", key));
+    }
+}
+
+/// Returns true if the translation has letters outside of its `%{placeholders}`,
+/// translations like `%{value}` are the same in every locale
+fn has_words(text: &str) -> bool {
+    let mut rest = text;
+    while let Some(start) = rest.find("%{") {
+        if rest[..start].chars().any(char::is_alphabetic) {
+            return true;
+        }
+        rest = rest[start..]
+            .find('}')
+            .map_or("", |end| &rest[start + end + 1..]);
+    }
+    rest.chars().any(char::is_alphabetic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locale_keys(locales: &[(&str, &[(&str, &str)])]) -> LocaleKeys {
+        locales
+            .iter()
+            .map(|(locale, translations)| {
+                let translations = translations
+                    .iter()
+                    .map(|(key, text)| (key.to_string(), text.to_string()))
+                    .collect();
+                (locale.to_string(), translations)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn coverage_and_unused_keys() {
+        let keys = locale_keys(&[
+            (
+                "en",
+                &[
+                    ("hello", "Hello"),
+                    ("messages.cats.one", "%{count} cat"),
+                    ("messages.cats.other", "%{count} cats"),
+                    ("dialogue.found.female", "She found it"),
+                    ("dialogue.found.other", "They found it"),
+                    ("ordinal.one", "%{number}st"),
+                    ("unused", "Unused"),
+                ],
+            ),
+            (
+                "fr",
+                &[
+                    ("hello", "Hello"),
+                    ("messages.cats.one", "%{count} chat"),
+                    ("ordinal.one", "%{number}er"),
+                ],
+            ),
+        ]);
+        let referenced = ["hello", "messages.cats", "dialogue.found"]
+            .into_iter()
+            .map(|key| (key.to_string(), Reference::default()))
+            .collect();
+        let report = Report::new(&keys, "en", &referenced);
+
+        assert_eq!(report.keys, 5);
+        assert_eq!(report.unused, ["unused"]);
+        let fr = &report.locales[0];
+        assert_eq!((fr.translated, fr.total), (3, 5));
+        assert_eq!(fr.missing, ["dialogue.found", "unused"]);
+        assert_eq!(fr.untranslated, ["hello"]);
+    }
+}
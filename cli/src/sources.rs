@@ -0,0 +1,430 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+use bevy_simple_i18n_parser::locale_keys::key_variants;
+
+/// Constructors whose first argument is a translation key
+const KEY_CALLS: &[&str] = &["I18nText::new(", "I18nText2d::new("];
+
+/// Constructors whose first argument is a variant of the `Keys` enum generated with the `typed_keys` feature
+const TYPED_KEY_CALLS: &[&str] = &["I18nText::from_key(", "I18nText2d::from_key("];
+
+/// Macro checking a translation key, its key is referenced even outside of a constructor
+const KEY_MACRO: &str = "i18n_key!(";
+
+/// Path of the variants of the `Keys` enum, they are referenced even outside of a constructor
+const TYPED_KEY: &str = "Keys::";
+
+/// Builder method whose first argument is the `msgctxt` of a gettext message
+const CONTEXT_METHOD: &str = "with_context";
+
+/// Builder methods whose first argument is the name of a placeholder
+const ARG_METHODS: &[&str] = &[
+    "with_arg",
+    "with_num_arg",
+    "with_number_arg",
+    "with_ordinal_arg",
+    "with_currency_arg",
+    "with_measure_arg",
+    "with_relative_time_arg",
+    "with_duration_arg",
+    "with_datetime_arg",
+    "with_list_arg",
+    "with_styled_list_arg",
+];
+
+/// Translation key referenced by the Rust sources
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Reference {
+    /// Names of the arguments the key is built with
+    pub args: BTreeSet<String>,
+    /// Contexts of the key, set with [CONTEXT_METHOD], keys with a context are `msgid`s of gettext catalogs
+    pub contexts: BTreeSet<String>,
+}
+
+impl Reference {
+    fn extend(&mut self, other: Reference) {
+        self.args.extend(other.args);
+        self.contexts.extend(other.contexts);
+    }
+}
+
+/// Returns the translation keys referenced by the Rust sources of the folders
+///
+/// `typed_keys` maps the variants of the `Keys` enum to their key, see [typed_keys]
+pub(crate) fn referenced_keys(
+    dirs: &[impl AsRef<Path>],
+    typed_keys: &BTreeMap<String, String>,
+) -> BTreeMap<String, Reference> {
+    let mut keys: BTreeMap<String, Reference> = BTreeMap::new();
+    for dir in dirs {
+        for path in rust_files(dir.as_ref()) {
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            for (key, reference) in keys_of_source(&content, typed_keys) {
+                keys.entry(key).or_default().extend(reference);
+            }
+        }
+    }
+    keys
+}
+
+/// Returns the keys of the variants of the `Keys` enum generated from the translations of the default locale,
+/// by variant, `MessagesHello` -> `messages.hello`
+pub(crate) fn typed_keys(translations: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+    key_variants(translations.keys().map(String::as_str))
+        .into_iter()
+        .map(|(key, variant)| (variant, key.to_string()))
+        .collect()
+}
+
+fn rust_files(dir: &Path) -> Vec<std::path::PathBuf> {
+    let mut files = vec![];
+    let Ok(entries) = fs::read_dir(dir) else {
+        return files;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            if path.file_name().is_some_and(|name| name != "target") {
+                files.append(&mut rust_files(&path));
+            }
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+    files
+}
+
+/// Returns the keys of the [KEY_CALLS], [TYPED_KEY_CALLS], [KEY_MACRO] and [TYPED_KEY]s of a source file,
+/// with the arguments of the [ARG_METHODS] and the [CONTEXT_METHOD] chained to the constructors
+///
+/// Comments, string literals and char literals are skipped
+fn keys_of_source(
+    content: &str,
+    typed_keys: &BTreeMap<String, String>,
+) -> Vec<(String, Reference)> {
+    let code = code_indices(content);
+    let matches = |pattern: &'static str| {
+        code.iter().copied().filter(move |&index| {
+            content[index..].starts_with(pattern)
+                && !content[..index].ends_with(|c: char| c.is_alphanumeric() || c == '_')
+        })
+    };
+    // `Keys::MessagesHello { name }` or `keys::Keys::MessagesHello` -> `messages.hello`
+    let typed_key = |source: &str| {
+        let source = skip_comments(source);
+        let path_end = source
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+            .unwrap_or(source.len());
+        let (path, variant) = source[..path_end].rsplit_once(TYPED_KEY)?;
+        if !(path.is_empty() || path.ends_with("::")) {
+            return None;
+        }
+        typed_keys.get(variant).cloned()
+    };
+
+    let mut keys = vec![];
+    for index in matches(KEY_MACRO) {
+        if let Some((key, _)) = string_literal(skip_comments(&content[index + KEY_MACRO.len()..])) {
+            keys.push((key, Reference::default()));
+        }
+    }
+    for index in matches(TYPED_KEY) {
+        if let Some(key) = typed_key(&content[index..]) {
+            keys.push((key, Reference::default()));
+        }
+    }
+
+    for call in KEY_CALLS.iter().chain(TYPED_KEY_CALLS) {
+        for index in matches(call) {
+            let rest = &content[index + call.len()..];
+            let key = if TYPED_KEY_CALLS.contains(call) {
+                typed_key(rest)
+            } else {
+                // `I18nText::new(i18n_key!("key"))`
+                let literal = skip_comments(rest);
+                let literal = literal.strip_prefix(KEY_MACRO).unwrap_or(literal);
+                string_literal(skip_comments(literal)).map(|(key, _)| key)
+            };
+            let (Some(key), Some(end)) = (key, call_end(rest)) else {
+                continue;
+            };
+            keys.push((key, chained_methods(&rest[end..])));
+        }
+    }
+    keys
+}
+
+/// Returns the placeholder names of the [ARG_METHODS] and the contexts of the [CONTEXT_METHOD] chained
+/// at the start of the source, `.with_arg("name", name).with_context("menu")` -> `["name"]`, `["menu"]`
+fn chained_methods(mut source: &str) -> Reference {
+    let mut reference = Reference::default();
+    while let Some(chain) = skip_comments(source).strip_prefix('.') {
+        let chain = skip_comments(chain);
+        let name_end = chain
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(chain.len());
+        let Some(call) = skip_comments(&chain[name_end..]).strip_prefix('(') else {
+            break;
+        };
+        let method = &chain[..name_end];
+        if let Some((value, _)) = string_literal(skip_comments(call)) {
+            if ARG_METHODS.contains(&method) {
+                reference.args.insert(value);
+            } else if method == CONTEXT_METHOD {
+                reference.contexts.insert(value);
+            }
+        }
+        let Some(end) = call_end(call) else {
+            break;
+        };
+        source = &call[end..];
+    }
+    reference
+}
+
+/// Returns the index after the `)` closing a call, the source starts right after its `(`
+fn call_end(source: &str) -> Option<usize> {
+    let mut depth = 1;
+    let mut index = 0;
+    while let Some(c) = source[index..].chars().next() {
+        if let Some(length) = non_code_length(&source[index..]) {
+            index += length;
+            continue;
+        }
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index + 1);
+                }
+            }
+            _ => {}
+        }
+        index += c.len_utf8();
+    }
+    None
+}
+
+/// Returns the indexes of the characters of the source that are code,
+/// outside of comments and of string and char literals
+fn code_indices(source: &str) -> Vec<usize> {
+    let mut indices = vec![];
+    let mut index = 0;
+    while let Some(c) = source[index..].chars().next() {
+        if let Some(length) = non_code_length(&source[index..]) {
+            index += length;
+            continue;
+        }
+        indices.push(index);
+        index += c.len_utf8();
+    }
+    indices
+}
+
+/// Returns the source without its leading whitespace and comments
+fn skip_comments(mut source: &str) -> &str {
+    loop {
+        source = source.trim_start();
+        if !(source.starts_with("//") || source.starts_with("/*")) {
+            return source;
+        }
+        source = &source[non_code_length(source).unwrap_or(source.len())..];
+    }
+}
+
+/// Returns the length of the comment, string literal or char literal at the start of the source
+fn non_code_length(source: &str) -> Option<usize> {
+    // Line and doc comments
+    if let Some(comment) = source.strip_prefix("//") {
+        return Some(2 + comment.find('\n').unwrap_or(comment.len()));
+    }
+    // Block comments, they can be nested
+    if source.starts_with("/*") {
+        let mut depth = 0;
+        let mut index = 0;
+        while index < source.len() {
+            let rest = &source[index..];
+            if rest.starts_with("/*") {
+                depth += 1;
+                index += 2;
+            } else if rest.starts_with("*/") {
+                depth -= 1;
+                index += 2;
+                if depth == 0 {
+                    return Some(index);
+                }
+            } else {
+                index += rest.chars().next()?.len_utf8();
+            }
+        }
+        return Some(source.len());
+    }
+    if source.starts_with(['"', 'r']) {
+        return string_literal(source).map(|(_, length)| length);
+    }
+    // `')'` or `'\''`, lifetimes like `'a` are code
+    let rest = source.strip_prefix('\'')?;
+    let first = rest.chars().next()?;
+    let after = if first == '\\' {
+        let escaped = rest[1..].chars().next()?;
+        1 + escaped.len_utf8() + rest[1 + escaped.len_utf8()..].find('\'')?
+    } else {
+        first.len_utf8()
+    };
+    rest[after..].starts_with('\'').then_some(1 + after + 1)
+}
+
+/// Parses the string literal at the start of the source, `"key"`, `r"key"` or `r#"key"#`,
+/// returns its value and its length in the source
+fn string_literal(source: &str) -> Option<(String, usize)> {
+    let trimmed = source.trim_start();
+    let offset = source.len() - trimmed.len();
+
+    if let Some(raw) = trimmed.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let raw = raw[hashes..].strip_prefix('"')?;
+        let closing = format!("\"{}", "#".repeat(hashes));
+        let end = raw.find(&closing)?;
+        let length = 1 + hashes + 1 + end + closing.len();
+        return Some((raw[..end].to_string(), offset + length));
+    }
+
+    let mut chars = trimmed.strip_prefix('"')?.char_indices();
+    let mut literal = String::new();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return Some((literal, offset + index + 2)),
+            '\\' => match chars.next()?.1 {
+                'n' => literal.push('\n'),
+                't' => literal.push('\t'),
+                c => literal.push(c),
+            },
+            c => literal.push(c),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(content: &str) -> BTreeMap<String, Reference> {
+        let typed_keys =
+            BTreeMap::from([("MessagesHello".to_string(), "messages.hello".to_string())]);
+        let mut keys: BTreeMap<String, Reference> = BTreeMap::new();
+        for (key, reference) in keys_of_source(content, &typed_keys) {
+            keys.entry(key).or_default().extend(reference);
+        }
+        keys
+    }
+
+    fn args(names: &[&str]) -> BTreeSet<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn string_literals() {
+        assert_eq!(
+            string_literal(r#""key" rest"#),
+            Some(("key".to_string(), 5))
+        );
+        assert_eq!(
+            string_literal(r#"  "a \"b\" \\ c\n""#),
+            Some(("a \"b\" \\ c\n".to_string(), 18))
+        );
+        assert_eq!(
+            string_literal(r##"r#"a "b""#"##),
+            Some(("a \"b\"".to_string(), 10))
+        );
+        assert_eq!(
+            string_literal(r#"r"raw\n""#),
+            Some(("raw\\n".to_string(), 8))
+        );
+        assert_eq!(string_literal(r#""unterminated"#), None);
+        assert_eq!(string_literal("name"), None);
+    }
+
+    #[test]
+    fn call_ends() {
+        assert_eq!(call_end(r#""key").with_arg("a", 1)"#), Some(6));
+        assert_eq!(call_end("a(b[c], {d})) rest"), Some(13));
+        // Delimiters of string literals, char literals and comments are not counted
+        assert_eq!(call_end(r#"")", ')', '\'') rest"#), Some(15));
+        assert_eq!(call_end("x /* ) */ // )\n) rest"), Some(16));
+        // Lifetimes aren't char literals
+        assert_eq!(call_end("&'a str, ')') rest"), Some(13));
+        assert_eq!(call_end(r#""key""#), None);
+    }
+
+    #[test]
+    fn chained_methods_of_a_constructor() {
+        let reference = chained_methods(
+            r#"
+            .with_arg("name", format!("{})", name))
+            // .with_arg("commented", 1)
+            .with_num_arg("count", 3)
+            .with_context("menu")
+            .with_font(font);"#,
+        );
+        assert_eq!(reference.args, args(&["count", "name"]));
+        assert_eq!(reference.contexts, args(&["menu"]));
+        assert_eq!(
+            chained_methods("; .with_arg(\"name\", 1)"),
+            Reference::default()
+        );
+    }
+
+    #[test]
+    fn keys_of_constructors_and_macros() {
+        let keys = keys(
+            r#"
+            commands.spawn(I18nText::new("hello").with_arg("name", "Bevy"));
+            commands.spawn(I18nText2d::new(i18n_key!("text2d")));
+            let key = i18n_key!("messages.cats");
+            "#,
+        );
+        assert_eq!(
+            keys.keys().collect::<Vec<_>>(),
+            ["hello", "messages.cats", "text2d"]
+        );
+        assert_eq!(keys["hello"].args, args(&["name"]));
+    }
+
+    #[test]
+    fn typed_keys_and_contexts() {
+        let keys = keys(
+            r#"
+            commands.spawn(I18nText::from_key(Keys::MessagesHello { name: "Bevy".into() }).with_arg("extra", 1));
+            commands.spawn(I18nText::from_key(keys::Keys::Unknown));
+            let key = crate::keys::Keys::MessagesHello { name };
+            commands.spawn(I18nText::new("Open").with_context("menu"));
+            "#,
+        );
+        assert_eq!(keys.keys().collect::<Vec<_>>(), ["Open", "messages.hello"]);
+        assert_eq!(keys["messages.hello"].args, args(&["extra"]));
+        assert_eq!(keys["Open"].contexts, args(&["menu"]));
+    }
+
+    #[test]
+    fn comments_and_strings_are_skipped() {
+        let keys = keys(
+            r##"
+            // commands.spawn(I18nText::new("line_comment"));
+            /// I18nText::new("doc_comment")
+            /* I18nText::new("block /* nested */ comment") */
+            let text = "I18nText::new(\"string\")";
+            let raw = r#"i18n_key!("raw")"#;
+            let text = MyI18nText::new("other_type");
+            commands.spawn(I18nText::new(/* key */ "kept"));
+            "##,
+        );
+        assert_eq!(keys.keys().collect::<Vec<_>>(), ["kept"]);
+    }
+}
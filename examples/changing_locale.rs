@@ -7,12 +7,22 @@ fn main() {
         .add_plugins(DefaultPlugins)
         .add_plugins(I18nPlugin)
         .add_systems(Startup, setup)
-        .add_systems(Update, button_system)
+        .add_systems(
+            Update,
+            (
+                spawn_locale_buttons.run_if(resource_changed::<I18n>),
+                button_system,
+            ),
+        )
         .run();
 }
 
-fn setup(mut commands: Commands, i18n_res: Res<I18n>) {
-    commands.spawn(Camera2d::default());
+/// Marker for the node that holds a button for every available locale
+#[derive(Component)]
+struct LocaleButtons;
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
     commands
         .spawn(Node {
             width: Val::Percent(100.),
@@ -51,7 +61,7 @@ fn setup(mut commands: Commands, i18n_res: Res<I18n>) {
                     parent.spawn((I18nNumber::new(24501.20), I18nFont::new("NotoSans")));
                 });
 
-            // Example that shows variable interpolation
+            // Localized currency
             parent
                 .spawn(Node {
                     display: Display::Flex,
@@ -59,14 +69,41 @@ fn setup(mut commands: Commands, i18n_res: Res<I18n>) {
                     ..Default::default()
                 })
                 .with_children(|parent| {
-                    parent.spawn(Text::new("'hello, %{name}' => "));
+                    parent.spawn(Text::new("24501.20 USD => "));
                     parent.spawn((
-                        I18nText::new("messages.hello").with_arg("name", "Bevy User"),
+                        I18nCurrency::new(24501.20, "USD"),
                         I18nFont::new("NotoSans"),
                     ));
                 });
 
-            // Example that shows variable interpolation with a number
+            // Localized date and time
+            parent
+                .spawn(Node {
+                    display: Display::Flex,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(Text::new("2025-01-02 15:04 => "));
+                    parent.spawn((
+                        I18nDateTime::new(2025, 1, 2).with_time(15, 4, 0),
+                        I18nFont::new("NotoSans"),
+                    ));
+                });
+
+            // Numbers formatted as ordinals with the patterns of the locale files
+            parent
+                .spawn(Node {
+                    display: Display::Flex,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(Text::new("3 (ordinal) => "));
+                    parent.spawn((I18nNumber::new(3).with_ordinal(), I18nFont::new("NotoSans")));
+                });
+
+            // Example that shows variable interpolation
             parent
                 .spawn(Node {
                     display: Display::Flex,
@@ -74,13 +111,31 @@ fn setup(mut commands: Commands, i18n_res: Res<I18n>) {
                     ..Default::default()
                 })
                 .with_children(|parent| {
-                    parent.spawn(Text::new("'You have %{count} cats' => "));
+                    parent.spawn(Text::new("'hello, %{name}' => "));
                     parent.spawn((
-                        I18nText::new("messages.cats").with_num_arg("count", 2000.30),
+                        I18nText::new("messages.hello").with_arg("name", "Bevy User"),
                         I18nFont::new("NotoSans"),
                     ));
                 });
 
+            // Example that shows variable interpolation with a number,
+            // the plural form of the translation is selected from the count
+            for count in [1., 3., 2000.30] {
+                parent
+                    .spawn(Node {
+                        display: Display::Flex,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(Text::new("'You have %{count} cats' => "));
+                        parent.spawn((
+                            I18nText::new("messages.cats").with_num_arg("count", count),
+                            I18nFont::new("NotoSans"),
+                        ));
+                    });
+            }
+
             // Spawns a node with a text that will always be in Japanese
             parent
                 .spawn(Node {
@@ -96,8 +151,9 @@ fn setup(mut commands: Commands, i18n_res: Res<I18n>) {
                     ));
                 });
 
-            parent
-                .spawn(Node {
+            parent.spawn((
+                LocaleButtons,
+                Node {
                     display: Display::Flex,
                     align_items: AlignItems::Center,
                     justify_content: JustifyContent::Center,
@@ -105,49 +161,65 @@ fn setup(mut commands: Commands, i18n_res: Res<I18n>) {
                     row_gap: Val::Px(10.),
                     column_gap: Val::Px(10.),
                     ..default()
-                })
-                .with_children(|parent| {
-                    for locale in i18n_res.locales() {
-                        parent
-                            .spawn((
-                                Button,
-                                Node {
-                                    min_width: Val::Px(200.0),
-                                    padding: UiRect::all(Val::Px(10.0)),
-                                    border: UiRect::all(Val::Px(5.0)),
-                                    justify_content: JustifyContent::Center,
-                                    align_items: AlignItems::Center,
-                                    ..default()
-                                },
-                                BorderColor(Color::BLACK),
-                                BorderRadius::MAX,
-                                BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
-                            ))
-                            .with_child((
-                                Text::new(locale),
-                                TextFont {
-                                    font_size: 50.0,
-                                    ..default()
-                                },
-                                TextColor(Color::srgb(0.9, 0.9, 0.9)),
-                            ));
-                    }
-                });
+                },
+            ));
         });
 }
 
+/// The available locales are only known once the locale files have been loaded,
+/// so the buttons are rebuilt whenever the list of locales changes
+fn spawn_locale_buttons(
+    mut commands: Commands,
+    i18n_res: Res<I18n>,
+    container: Single<(Entity, Option<&Children>), With<LocaleButtons>>,
+) {
+    let (entity, children) = *container;
+    if children.map_or(0, |children| children.len()) == i18n_res.locales().len() {
+        return;
+    }
+    commands
+        .entity(entity)
+        .despawn_related::<Children>()
+        .with_children(|parent| {
+            for locale in i18n_res.locales() {
+                parent
+                    .spawn((
+                        Button,
+                        Node {
+                            min_width: Val::Px(200.0),
+                            padding: UiRect::all(Val::Px(10.0)),
+                            border: UiRect::all(Val::Px(5.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        BorderColor(Color::BLACK),
+                        BorderRadius::MAX,
+                        BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
+                    ))
+                    .with_child((
+                        Text::new(locale),
+                        TextFont {
+                            font_size: 50.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    ));
+            }
+        });
+}
+
+type ButtonFilter = (Changed<Interaction>, With<Button>);
+
 fn button_system(
-    interaction_query: Query<(&Interaction, &Children), (Changed<Interaction>, With<Button>)>,
+    interaction_query: Query<(&Interaction, &Children), ButtonFilter>,
     text_query: Query<&Text>,
     mut i18n_res: ResMut<I18n>,
 ) {
     for (interaction, children) in interaction_query.iter() {
-        match *interaction {
-            Interaction::Pressed => {
-                let text = text_query.get(children[0]).unwrap().clone().0;
-                i18n_res.set_locale(text);
-            }
-            _ => {}
+        if *interaction == Interaction::Pressed {
+            let text = text_query.get(children[0]).unwrap().clone().0;
+            i18n_res.set_locale(text);
         }
     }
 }
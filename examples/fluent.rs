@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+use bevy_simple_i18n::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(I18nPlugin)
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+    commands
+        .spawn(Node {
+            width: Val::Percent(100.),
+            height: Val::Percent(100.),
+            display: Display::Flex,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(20.),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            // Fluent messages are resolved by their id, string arguments become Fluent variables
+            parent.spawn((
+                I18nText::new("fluent-welcome").with_arg("name", "Bevy User"),
+                I18nFont::new("NotoSans"),
+            ));
+
+            // Number arguments stay numbers, so they can be used by Fluent selectors
+            parent.spawn((
+                I18nText::new("fluent-cats").with_num_arg("count", 1),
+                I18nFont::new("NotoSans"),
+            ));
+            parent.spawn((
+                I18nText::new("fluent-cats").with_num_arg("count", 3),
+                I18nFont::new("NotoSans"),
+            ));
+
+            // Attributes are resolved with `id.attribute`
+            parent.spawn((
+                I18nText::new("fluent-cats.title").with_locale("ja"),
+                I18nFont::new("NotoSans"),
+            ));
+        });
+}
@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+
+use bevy_simple_i18n::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(I18nPlugin)
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+    commands
+        .spawn(Node {
+            width: Val::Percent(100.),
+            height: Val::Percent(100.),
+            display: Display::Flex,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(20.),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            // Gettext messages are resolved by their `msgid`
+            parent.spawn((
+                I18nText::new("Hello, %{name}")
+                    .with_arg("name", "Bevy User")
+                    .with_locale("de"),
+                I18nFont::new("NotoSans"),
+            ));
+
+            // The context selects the `msgctxt` of messages sharing the same `msgid`
+            parent.spawn((
+                I18nText::new("Open").with_context("menu").with_locale("de"),
+                I18nFont::new("NotoSans"),
+            ));
+            parent.spawn((
+                I18nText::new("Open").with_context("door").with_locale("de"),
+                I18nFont::new("NotoSans"),
+            ));
+
+            // The `count` argument selects the plural form with the `Plural-Forms` header
+            parent.spawn((
+                I18nText::new("You have %{count} cat")
+                    .with_num_arg("count", 1)
+                    .with_locale("de"),
+                I18nFont::new("NotoSans"),
+            ));
+            parent.spawn((
+                I18nText::new("You have %{count} cat")
+                    .with_num_arg("count", 3)
+                    .with_locale("de"),
+                I18nFont::new("NotoSans"),
+            ));
+        });
+}
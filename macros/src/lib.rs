@@ -0,0 +1,130 @@
+//! Procedural macros of [bevy_simple_i18n](https://docs.rs/bevy_simple_i18n), use them through the main crate
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    bracketed, parenthesized,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    LitStr, Token,
+};
+
+/// Minimum similarity of a known key to be suggested for an unknown key
+const SUGGESTION_THRESHOLD: f64 = 0.7;
+
+/// Maximum number of suggested keys
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Arguments passed by the `i18n_key!` macro generated by the build script
+///
+/// `__i18n_key_error!("key", "en", ["default keys", ..], [("other keys", "de, ja"), ..])`
+struct KeyCheck {
+    key: LitStr,
+    default_locale: LitStr,
+    default_keys: Vec<LitStr>,
+    other_keys: Vec<(LitStr, LitStr)>,
+}
+
+impl Parse for KeyCheck {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let default_locale = input.parse()?;
+        input.parse::<Token![,]>()?;
+
+        let content;
+        bracketed!(content in input);
+        let default_keys = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?
+            .into_iter()
+            .collect();
+        input.parse::<Token![,]>()?;
+
+        let content;
+        bracketed!(content in input);
+        let other_keys = Punctuated::<OtherKey, Token![,]>::parse_terminated(&content)?
+            .into_iter()
+            .map(|other| (other.key, other.locales))
+            .collect();
+
+        Ok(Self {
+            key,
+            default_locale,
+            default_keys,
+            other_keys,
+        })
+    }
+}
+
+/// Key missing from the default locale, with the locales it was found in
+struct OtherKey {
+    key: LitStr,
+    locales: LitStr,
+}
+
+impl Parse for OtherKey {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let key = content.parse()?;
+        content.parse::<Token![,]>()?;
+        let locales = content.parse()?;
+        Ok(Self { key, locales })
+    }
+}
+
+/// Reports the translation keys that did not match a known key of the `i18n_key!` macro
+///
+/// Literals written differently, e.g. raw strings, are still checked and returned as is
+#[doc(hidden)]
+#[proc_macro]
+pub fn i18n_key_error(input: TokenStream) -> TokenStream {
+    let check = parse_macro_input!(input as KeyCheck);
+    let key = check.key.value();
+
+    if check.default_keys.iter().any(|known| known.value() == key) {
+        let lit = &check.key;
+        return quote!(#lit).into();
+    }
+
+    let message = if let Some((_, locales)) = check
+        .other_keys
+        .iter()
+        .find(|(other, _)| other.value() == key)
+    {
+        format!(
+            "Translation key `{}` is missing from the default locale `{}`, it was only found in: {}",
+            key,
+            check.default_locale.value(),
+            locales.value()
+        )
+    } else {
+        let mut suggestions: Vec<(f64, String)> = check
+            .default_keys
+            .iter()
+            .chain(check.other_keys.iter().map(|(other, _)| other))
+            .map(|known| {
+                let known = known.value();
+                (strsim::normalized_damerau_levenshtein(&key, &known), known)
+            })
+            .filter(|(score, _)| *score >= SUGGESTION_THRESHOLD)
+            .collect();
+        suggestions.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+
+        let mut message = format!("Unknown translation key `{}`", key);
+        if !suggestions.is_empty() {
+            let suggestions = suggestions
+                .into_iter()
+                .take(MAX_SUGGESTIONS)
+                .map(|(_, known)| format!("`{}`", known))
+                .collect::<Vec<_>>()
+                .join(", ");
+            message.push_str(&format!(", did you mean {}?", suggestions));
+        }
+        message
+    };
+
+    syn::Error::new(check.key.span(), message)
+        .to_compile_error()
+        .into()
+}
@@ -0,0 +1,137 @@
+use super::{Catalog, ParseError};
+
+const MAGIC: u32 = 0x950412de;
+/// Separates the `msgctxt` from the `msgid` in compiled catalogs
+const CONTEXT_SEPARATOR: char = '\u{4}';
+
+/// Parses the content of a compiled `.mo` file
+pub(super) fn parse(bytes: &[u8]) -> Result<Catalog, ParseError> {
+    let read_u32 = |offset: usize, big_endian: bool| -> Result<u32, ParseError> {
+        let word: [u8; 4] = bytes
+            .get(offset..offset + 4)
+            .and_then(|word| word.try_into().ok())
+            .ok_or(ParseError::InvalidMo("unexpected end of file"))?;
+        Ok(if big_endian {
+            u32::from_be_bytes(word)
+        } else {
+            u32::from_le_bytes(word)
+        })
+    };
+
+    let big_endian = match read_u32(0, false)? {
+        MAGIC => false,
+        magic if magic.swap_bytes() == MAGIC => true,
+        _ => return Err(ParseError::InvalidMo("invalid magic number")),
+    };
+    let count = read_u32(8, big_endian)? as usize;
+    let originals = read_u32(12, big_endian)? as usize;
+    let translations = read_u32(16, big_endian)? as usize;
+
+    let read_string = |table: usize, index: usize| -> Result<&str, ParseError> {
+        let length = read_u32(table + index * 8, big_endian)? as usize;
+        let offset = read_u32(table + index * 8 + 4, big_endian)? as usize;
+        let string = bytes
+            .get(offset..offset + length)
+            .ok_or(ParseError::InvalidMo("string out of bounds"))?;
+        std::str::from_utf8(string).map_err(|_| ParseError::InvalidMo("invalid UTF-8"))
+    };
+
+    let mut catalog = Catalog::default();
+    for index in 0..count {
+        let original = read_string(originals, index)?;
+        let translation = read_string(translations, index)?;
+
+        let (context, original) = match original.split_once(CONTEXT_SEPARATOR) {
+            Some((context, original)) => (Some(context.to_string()), original),
+            None => (None, original),
+        };
+        // `msgid\0msgid_plural`
+        let id = original.split('\0').next().unwrap_or_default();
+        let strs = translation.split('\0').map(str::to_string).collect();
+        catalog.insert(context, id.to_string(), strs);
+    }
+
+    Ok(catalog)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Compiles the messages into a `.mo` file with the provided byte order
+    fn compile(messages: &[(&str, &str)], big_endian: bool) -> Vec<u8> {
+        let word = |value: usize| {
+            let value = value as u32;
+            if big_endian {
+                value.to_be_bytes()
+            } else {
+                value.to_le_bytes()
+            }
+        };
+        let count = messages.len();
+        let originals = 28;
+        let translations = originals + count * 8;
+        let mut strings_offset = translations + count * 8;
+
+        let mut bytes = vec![];
+        for value in [MAGIC as usize, 0, count, originals, translations, 0, 0] {
+            bytes.extend(word(value));
+        }
+        let mut strings = vec![];
+        for table in [0, 1] {
+            for message in messages {
+                let string = if table == 0 { message.0 } else { message.1 };
+                bytes.extend(word(string.len()));
+                bytes.extend(word(strings_offset));
+                strings.extend(string.as_bytes());
+                strings.push(0);
+                strings_offset += string.len() + 1;
+            }
+        }
+        bytes.extend(strings);
+        bytes
+    }
+
+    #[test]
+    fn both_byte_orders() {
+        let messages = [
+            (
+                "",
+                "Language: pt_BR\nPlural-Forms: nplurals=2; plural=(n > 1);\n",
+            ),
+            ("Hello", "Olá"),
+            ("menu\u{4}Open", "Abrir"),
+            ("cat\0cats", "gato\0gatos"),
+        ];
+        for big_endian in [false, true] {
+            let catalog = parse(&compile(&messages, big_endian)).unwrap();
+            assert_eq!(catalog.language.as_deref(), Some("pt-BR"));
+            assert_eq!(catalog.plural_forms.as_ref().unwrap().index(1), 0);
+            assert_eq!(
+                catalog.messages.get(&(None, "Hello".to_string())),
+                Some(&vec!["Olá".to_string()])
+            );
+            assert_eq!(
+                catalog
+                    .messages
+                    .get(&(Some("menu".to_string()), "Open".to_string())),
+                Some(&vec!["Abrir".to_string()])
+            );
+            assert_eq!(
+                catalog.messages.get(&(None, "cat".to_string())),
+                Some(&vec!["gato".to_string(), "gatos".to_string()])
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_files() {
+        assert!(matches!(
+            parse(&[0; 4]),
+            Err(ParseError::InvalidMo("invalid magic number"))
+        ));
+        let mut truncated = compile(&[("Hello", "Hallo")], false);
+        truncated.truncate(40);
+        assert!(matches!(parse(&truncated), Err(ParseError::InvalidMo(_))));
+    }
+}
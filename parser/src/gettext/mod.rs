@@ -0,0 +1,114 @@
+//! Parsers of gettext catalogs, `.po` files and compiled `.mo` files
+
+mod mo;
+mod plural;
+mod po;
+
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+pub use plural::PluralForms;
+
+/// Errors of the gettext parsers
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    #[error("Invalid PO file at line {0}: {1}")]
+    InvalidPo(usize, String),
+    #[error("Invalid MO file: {0}")]
+    InvalidMo(&'static str),
+}
+
+/// Messages of a gettext catalog by `msgctxt` and `msgid`
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    language: Option<String>,
+    plural_forms: Option<PluralForms>,
+    invalid_plural_forms: Option<String>,
+    messages: BTreeMap<(Option<String>, String), Vec<String>>,
+}
+
+impl Catalog {
+    fn insert(&mut self, context: Option<String>, id: String, strs: Vec<String>) {
+        if id.is_empty() && context.is_none() {
+            self.parse_header(strs.first().map(String::as_str).unwrap_or_default());
+            return;
+        }
+        // Untranslated entries are treated as missing
+        if strs.iter().all(String::is_empty) {
+            return;
+        }
+        self.messages.insert((context, id), strs);
+    }
+
+    fn parse_header(&mut self, header: &str) {
+        for line in header.lines() {
+            let Some((name, value)) = line.split_once(':') else {
+                continue;
+            };
+            match name.trim() {
+                "Language" => {
+                    self.language =
+                        Some(normalize_locale(value.trim())).filter(|language| !language.is_empty())
+                }
+                "Plural-Forms" => {
+                    self.plural_forms = PluralForms::parse(value);
+                    self.invalid_plural_forms = self
+                        .plural_forms
+                        .is_none()
+                        .then(|| value.trim().to_string());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the locale of the `Language` header, see [normalize_locale]
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Returns the plural rules of the `Plural-Forms` header
+    pub fn plural_forms(&self) -> Option<&PluralForms> {
+        self.plural_forms.as_ref()
+    }
+
+    /// Returns the `Plural-Forms` header if it couldn't be parsed
+    pub fn invalid_plural_forms(&self) -> Option<&str> {
+        self.invalid_plural_forms.as_deref()
+    }
+
+    /// Returns the translations of the message, one for each plural form
+    pub fn get(&self, context: Option<&str>, id: &str) -> Option<&[String]> {
+        self.messages
+            .get(&(context.map(str::to_string), id.to_string()))
+            .map(Vec::as_slice)
+    }
+
+    /// Returns the `msgid`s of the messages with their translations,
+    /// the plural forms are kept together, `msgstr[0]\nmsgstr[1]`
+    pub fn messages(&self) -> impl Iterator<Item = (&str, String)> {
+        self.messages
+            .iter()
+            .map(|((_, id), strs)| (id.as_str(), strs.join("\n")))
+    }
+}
+
+/// Parses the content of a `.po` file
+pub fn parse_po(content: &str) -> Result<Catalog, ParseError> {
+    po::parse(content)
+}
+
+/// Parses the content of a compiled `.mo` file
+pub fn parse_mo(bytes: &[u8]) -> Result<Catalog, ParseError> {
+    mo::parse(bytes)
+}
+
+/// Converts gettext locales to BCP-47, `sr_RS@latin` -> `sr-RS`
+pub fn normalize_locale(locale: &str) -> String {
+    locale
+        .split(['@', '.'])
+        .next()
+        .unwrap_or_default()
+        .replace('_', "-")
+}
@@ -0,0 +1,331 @@
+/// Parsed `plural=` expression of a `Plural-Forms` header
+///
+/// The expression uses the C syntax supported by gettext, e.g. `(n != 1)` or
+/// `(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2)`
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PluralExpr {
+    N,
+    Number(u64),
+    Not(Box<PluralExpr>),
+    Binary(Box<PluralExpr>, Op, Box<PluralExpr>),
+    Ternary(Box<PluralExpr>, Box<PluralExpr>, Box<PluralExpr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Op {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl Op {
+    /// Binding power of the operator, higher binds tighter
+    fn precedence(self) -> u8 {
+        match self {
+            Op::Or => 1,
+            Op::And => 2,
+            Op::Eq | Op::Ne => 3,
+            Op::Lt | Op::Le | Op::Gt | Op::Ge => 4,
+            Op::Add | Op::Sub => 5,
+            Op::Mul | Op::Div | Op::Rem => 6,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    N,
+    Number(u64),
+    Op(Op),
+    Not,
+    Question,
+    Colon,
+    Open,
+    Close,
+}
+
+/// Plural rules of a catalog, `nplurals=2; plural=(n != 1);` by default
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluralForms {
+    pub(crate) nplurals: usize,
+    pub(crate) expr: PluralExpr,
+}
+
+impl Default for PluralForms {
+    fn default() -> Self {
+        Self {
+            nplurals: 2,
+            expr: PluralExpr::Binary(
+                Box::new(PluralExpr::N),
+                Op::Ne,
+                Box::new(PluralExpr::Number(1)),
+            ),
+        }
+    }
+}
+
+impl PluralForms {
+    /// Parses the value of a `Plural-Forms` header, e.g. `nplurals=2; plural=(n != 1);`
+    pub fn parse(header: &str) -> Option<Self> {
+        let mut nplurals = None;
+        let mut expr = None;
+        for part in header.split(';') {
+            let Some((name, value)) = part.split_once('=') else {
+                continue;
+            };
+            match name.trim() {
+                "nplurals" => nplurals = value.trim().parse().ok(),
+                "plural" => expr = PluralExpr::parse(value.trim()),
+                _ => {}
+            }
+        }
+        Some(Self {
+            nplurals: nplurals?,
+            expr: expr?,
+        })
+    }
+
+    /// Returns the index of the `msgstr[n]` form to use for the count
+    pub fn index(&self, n: u64) -> usize {
+        (self.expr.eval(n) as usize).min(self.nplurals.saturating_sub(1))
+    }
+}
+
+impl PluralExpr {
+    pub(crate) fn parse(source: &str) -> Option<Self> {
+        let tokens = tokenize(source)?;
+        let mut pos = 0;
+        let expr = parse_ternary(&tokens, &mut pos)?;
+        (pos == tokens.len()).then_some(expr)
+    }
+
+    pub(crate) fn eval(&self, n: u64) -> u64 {
+        match self {
+            PluralExpr::N => n,
+            PluralExpr::Number(value) => *value,
+            PluralExpr::Not(expr) => (expr.eval(n) == 0) as u64,
+            PluralExpr::Ternary(condition, then, otherwise) => {
+                if condition.eval(n) != 0 {
+                    then.eval(n)
+                } else {
+                    otherwise.eval(n)
+                }
+            }
+            PluralExpr::Binary(lhs, op, rhs) => {
+                let (lhs, rhs) = (lhs.eval(n), rhs.eval(n));
+                match op {
+                    Op::Or => (lhs != 0 || rhs != 0) as u64,
+                    Op::And => (lhs != 0 && rhs != 0) as u64,
+                    Op::Eq => (lhs == rhs) as u64,
+                    Op::Ne => (lhs != rhs) as u64,
+                    Op::Lt => (lhs < rhs) as u64,
+                    Op::Le => (lhs <= rhs) as u64,
+                    Op::Gt => (lhs > rhs) as u64,
+                    Op::Ge => (lhs >= rhs) as u64,
+                    Op::Add => lhs.wrapping_add(rhs),
+                    Op::Sub => lhs.wrapping_sub(rhs),
+                    Op::Mul => lhs.wrapping_mul(rhs),
+                    Op::Div => lhs.checked_div(rhs).unwrap_or_default(),
+                    Op::Rem => lhs.checked_rem(rhs).unwrap_or_default(),
+                }
+            }
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Option<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            'n' => Token::N,
+            '0'..='9' => {
+                let mut value = c.to_digit(10)? as u64;
+                while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+                    // Numbers that don't fit in a `u64` make the expression invalid
+                    value = value.checked_mul(10)?.checked_add(digit as u64)?;
+                    chars.next();
+                }
+                Token::Number(value)
+            }
+            '?' => Token::Question,
+            ':' => Token::Colon,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '+' => Token::Op(Op::Add),
+            '-' => Token::Op(Op::Sub),
+            '*' => Token::Op(Op::Mul),
+            '/' => Token::Op(Op::Div),
+            '%' => Token::Op(Op::Rem),
+            '|' if chars.next_if_eq(&'|').is_some() => Token::Op(Op::Or),
+            '&' if chars.next_if_eq(&'&').is_some() => Token::Op(Op::And),
+            '=' if chars.next_if_eq(&'=').is_some() => Token::Op(Op::Eq),
+            '!' if chars.next_if_eq(&'=').is_some() => Token::Op(Op::Ne),
+            '!' => Token::Not,
+            '<' if chars.next_if_eq(&'=').is_some() => Token::Op(Op::Le),
+            '<' => Token::Op(Op::Lt),
+            '>' if chars.next_if_eq(&'=').is_some() => Token::Op(Op::Ge),
+            '>' => Token::Op(Op::Gt),
+            _ => return None,
+        };
+        tokens.push(token);
+    }
+    Some(tokens)
+}
+
+fn parse_ternary(tokens: &[Token], pos: &mut usize) -> Option<PluralExpr> {
+    let condition = parse_binary(tokens, pos, 0)?;
+    if tokens.get(*pos) != Some(&Token::Question) {
+        return Some(condition);
+    }
+    *pos += 1;
+    let then = parse_ternary(tokens, pos)?;
+    if tokens.get(*pos) != Some(&Token::Colon) {
+        return None;
+    }
+    *pos += 1;
+    let otherwise = parse_ternary(tokens, pos)?;
+    Some(PluralExpr::Ternary(
+        Box::new(condition),
+        Box::new(then),
+        Box::new(otherwise),
+    ))
+}
+
+fn parse_binary(tokens: &[Token], pos: &mut usize, min_precedence: u8) -> Option<PluralExpr> {
+    let mut lhs = parse_unary(tokens, pos)?;
+    while let Some(Token::Op(op)) = tokens.get(*pos) {
+        let op = *op;
+        if op.precedence() <= min_precedence {
+            break;
+        }
+        *pos += 1;
+        let rhs = parse_binary(tokens, pos, op.precedence())?;
+        lhs = PluralExpr::Binary(Box::new(lhs), op, Box::new(rhs));
+    }
+    Some(lhs)
+}
+
+fn parse_unary(tokens: &[Token], pos: &mut usize) -> Option<PluralExpr> {
+    let token = tokens.get(*pos)?;
+    *pos += 1;
+    match token {
+        Token::N => Some(PluralExpr::N),
+        Token::Number(value) => Some(PluralExpr::Number(*value)),
+        Token::Not => Some(PluralExpr::Not(Box::new(parse_unary(tokens, pos)?))),
+        Token::Open => {
+            let expr = parse_ternary(tokens, pos)?;
+            if tokens.get(*pos) != Some(&Token::Close) {
+                return None;
+            }
+            *pos += 1;
+            Some(expr)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indexes(header: &str, counts: &[u64]) -> Vec<usize> {
+        let forms = PluralForms::parse(header).unwrap();
+        counts.iter().map(|n| forms.index(*n)).collect()
+    }
+
+    fn eval(source: &str, n: u64) -> u64 {
+        PluralExpr::parse(source).unwrap().eval(n)
+    }
+
+    #[test]
+    fn russian_rule() {
+        let header = "nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2);";
+        assert_eq!(
+            indexes(header, &[1, 2, 5, 11, 21, 22, 111, 0]),
+            [0, 1, 2, 2, 0, 1, 2, 2]
+        );
+    }
+
+    #[test]
+    fn polish_rule() {
+        let header = "nplurals=3; plural=(n==1 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2);";
+        assert_eq!(
+            indexes(header, &[1, 2, 4, 5, 12, 22, 0]),
+            [0, 1, 1, 2, 2, 1, 2]
+        );
+    }
+
+    #[test]
+    fn arabic_rule() {
+        let header = "nplurals=6; plural=(n==0 ? 0 : n==1 ? 1 : n==2 ? 2 : n%100>=3 && n%100<=10 ? 3 : n%100>=11 ? 4 : 5);";
+        assert_eq!(
+            indexes(header, &[0, 1, 2, 3, 10, 11, 99, 100, 102]),
+            [0, 1, 2, 3, 3, 4, 4, 5, 5]
+        );
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(eval("n + 2 * 3", 1), 7);
+        assert_eq!(eval("(n + 2) * 3", 1), 9);
+        assert_eq!(eval("10 - n - 3", 2), 5);
+        assert_eq!(eval("n % 10 == 1 || n == 5 && n == 6", 5), 0);
+        assert_eq!(eval("n < 2 == 1", 1), 1);
+        assert_eq!(eval("!n", 0), 1);
+        assert_eq!(eval("!n + 1", 3), 1);
+    }
+
+    #[test]
+    fn nested_ternaries() {
+        let source = "n == 1 ? 0 : n == 2 ? 1 : n > 10 ? (n > 100 ? 4 : 3) : 2";
+        let results: Vec<u64> = [1, 2, 5, 11, 101]
+            .iter()
+            .map(|n| eval(source, *n))
+            .collect();
+        assert_eq!(results, [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn division_by_zero_is_zero() {
+        assert_eq!(eval("n / 0", 5), 0);
+        assert_eq!(eval("n % 0", 5), 0);
+    }
+
+    #[test]
+    fn index_is_clamped_to_the_plural_forms() {
+        assert_eq!(indexes("nplurals=2; plural=n;", &[0, 1, 5]), [0, 1, 1]);
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        for source in ["n ==", "(n", "n ? 1", "n ? 1 : ", "n = 1", "n & 1", "x", ""] {
+            assert_eq!(PluralExpr::parse(source), None, "{}", source);
+        }
+        assert_eq!(PluralForms::parse("plural=(n != 1);"), None);
+    }
+
+    #[test]
+    fn overflowing_numbers_are_invalid() {
+        assert_eq!(PluralExpr::parse("n > 99999999999999999999"), None);
+        assert_eq!(
+            PluralExpr::parse("n > 18446744073709551615"),
+            Some(PluralExpr::Binary(
+                Box::new(PluralExpr::N),
+                Op::Gt,
+                Box::new(PluralExpr::Number(u64::MAX))
+            ))
+        );
+    }
+}
@@ -0,0 +1,295 @@
+use super::{Catalog, ParseError};
+
+/// Maximum number of plural forms of a message, Arabic has the most with 6
+const MAX_PLURAL_FORMS: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Context,
+    Id,
+    IdPlural,
+    Str(usize),
+}
+
+/// Entry being parsed, flushed into the catalog on the next entry or at the end of the file
+#[derive(Debug, Default)]
+struct Entry {
+    context: Option<String>,
+    id: Option<String>,
+    id_plural: Option<String>,
+    strs: Vec<String>,
+    fuzzy: bool,
+    has_str: bool,
+}
+
+impl Entry {
+    fn flush(&mut self, catalog: &mut Catalog) {
+        let entry = std::mem::take(self);
+        // Like `msgfmt`, fuzzy entries are not used
+        if entry.fuzzy && entry.id.as_deref() != Some("") {
+            return;
+        }
+        if let Some(id) = entry.id {
+            catalog.insert(entry.context, id, entry.strs);
+        }
+    }
+
+    fn field_mut(&mut self, field: Field) -> &mut String {
+        match field {
+            Field::Context => self.context.get_or_insert_with(String::new),
+            Field::Id => self.id.get_or_insert_with(String::new),
+            Field::IdPlural => self.id_plural.get_or_insert_with(String::new),
+            Field::Str(index) => {
+                if self.strs.len() <= index {
+                    self.strs.resize(index + 1, String::new());
+                }
+                &mut self.strs[index]
+            }
+        }
+    }
+}
+
+/// Parses the content of a `.po` file
+pub(super) fn parse(content: &str) -> Result<Catalog, ParseError> {
+    let mut catalog = Catalog::default();
+    let mut entry = Entry::default();
+    let mut field = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        let invalid = || ParseError::InvalidPo(index + 1, line.to_string());
+
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            // Obsolete entries are ignored
+            if comment.starts_with('~') {
+                field = None;
+                continue;
+            }
+            if let Some(flags) = comment.strip_prefix(',') {
+                if entry.has_str {
+                    entry.flush(&mut catalog);
+                }
+                entry.fuzzy |= flags.split(',').any(|flag| flag.trim() == "fuzzy");
+            }
+            continue;
+        }
+        if line.starts_with('"') {
+            let Some(field) = field else {
+                return Err(invalid());
+            };
+            entry
+                .field_mut(field)
+                .push_str(&unquote(line).ok_or_else(invalid)?);
+            continue;
+        }
+
+        let (keyword, value) = line.split_once(char::is_whitespace).ok_or_else(invalid)?;
+        let next_field = match keyword {
+            "msgctxt" => Field::Context,
+            "msgid" => Field::Id,
+            "msgid_plural" => Field::IdPlural,
+            "msgstr" => Field::Str(0),
+            _ => keyword
+                .strip_prefix("msgstr[")
+                .and_then(|rest| rest.strip_suffix(']'))
+                .and_then(|index| index.parse().ok())
+                .map(Field::Str)
+                .ok_or_else(invalid)?,
+        };
+        // `msgstr[n]` must be one of the plural forms of the catalog
+        if let Field::Str(index) = next_field {
+            let nplurals = catalog
+                .plural_forms
+                .as_ref()
+                .map_or(MAX_PLURAL_FORMS, |forms| {
+                    forms.nplurals.min(MAX_PLURAL_FORMS)
+                });
+            if index >= nplurals {
+                return Err(invalid());
+            }
+        }
+        // A new `msgctxt` or `msgid` after a `msgstr` starts the next entry
+        if matches!(next_field, Field::Context | Field::Id) && entry.has_str {
+            entry.flush(&mut catalog);
+        }
+        if let Field::Str(_) = next_field {
+            entry.has_str = true;
+        }
+        let value = unquote(value.trim()).ok_or_else(invalid)?;
+        entry.field_mut(next_field).push_str(&value);
+        field = Some(next_field);
+    }
+    entry.flush(&mut catalog);
+
+    Ok(catalog)
+}
+
+/// Removes the quotes of a PO string and resolves its escape sequences
+fn unquote(value: &str) -> Option<String> {
+    let inner = value.strip_prefix('"')?.strip_suffix('"')?;
+    let mut result = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => result.push('\n'),
+            't' => result.push('\t'),
+            'r' => result.push('\r'),
+            'a' => result.push('\u{7}'),
+            'b' => result.push('\u{8}'),
+            'f' => result.push('\u{c}'),
+            'v' => result.push('\u{b}'),
+            other => result.push(other),
+        }
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = r#"msgid ""
+msgstr ""
+"Language: de\n"
+"Plural-Forms: nplurals=2; plural=(n != 1);\n"
+"#;
+
+    fn message<'a>(catalog: &'a Catalog, context: Option<&str>, id: &str) -> Option<&'a [String]> {
+        catalog
+            .messages
+            .get(&(context.map(str::to_string), id.to_string()))
+            .map(Vec::as_slice)
+    }
+
+    #[test]
+    fn header() {
+        let catalog = parse(HEADER).unwrap();
+        assert_eq!(catalog.language.as_deref(), Some("de"));
+        assert_eq!(catalog.plural_forms.unwrap().nplurals, 2);
+        assert!(catalog.messages.is_empty());
+    }
+
+    #[test]
+    fn escapes_and_continuation_lines() {
+        let catalog = parse(
+            r#"
+msgid "quote"
+msgstr "\"a\" \\ b\tc"
+"\nd"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            message(&catalog, None, "quote"),
+            Some(["\"a\" \\ b\tc\nd".to_string()].as_slice())
+        );
+    }
+
+    #[test]
+    fn contexts() {
+        let catalog = parse(
+            r#"
+msgctxt "menu"
+msgid "Open"
+msgstr "Öffnen"
+
+msgid "Open"
+msgstr "Offen"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            message(&catalog, Some("menu"), "Open"),
+            Some(["Öffnen".to_string()].as_slice())
+        );
+        assert_eq!(
+            message(&catalog, None, "Open"),
+            Some(["Offen".to_string()].as_slice())
+        );
+    }
+
+    #[test]
+    fn plural_forms() {
+        let catalog = parse(&format!(
+            r#"{}
+msgid "%{{count}} cat"
+msgid_plural "%{{count}} cats"
+msgstr[0] "%{{count}} Katze"
+msgstr[1] "%{{count}} Katzen"
+"#,
+            HEADER
+        ))
+        .unwrap();
+        assert_eq!(
+            message(&catalog, None, "%{count} cat"),
+            Some(["%{count} Katze".to_string(), "%{count} Katzen".to_string()].as_slice())
+        );
+    }
+
+    #[test]
+    fn fuzzy_and_obsolete_entries_are_skipped() {
+        let catalog = parse(
+            r#"
+#, fuzzy
+msgid "Guess"
+msgstr "Vermutung"
+
+#~ msgid "Old"
+#~ msgstr "Alt"
+
+#, c-format
+msgid "Kept"
+msgstr "Behalten"
+"#,
+        )
+        .unwrap();
+        assert_eq!(message(&catalog, None, "Guess"), None);
+        assert_eq!(message(&catalog, None, "Old"), None);
+        assert_eq!(
+            message(&catalog, None, "Kept"),
+            Some(["Behalten".to_string()].as_slice())
+        );
+    }
+
+    #[test]
+    fn untranslated_entries_are_skipped() {
+        let catalog = parse("msgid \"Empty\"\nmsgstr \"\"\n").unwrap();
+        assert_eq!(message(&catalog, None, "Empty"), None);
+    }
+
+    #[test]
+    fn plural_index_out_of_the_plural_forms() {
+        let content = format!(
+            "{}\nmsgid \"cat\"\nmsgid_plural \"cats\"\nmsgstr[2] \"Katzen\"\n",
+            HEADER
+        );
+        assert!(matches!(parse(&content), Err(ParseError::InvalidPo(8, _))));
+        assert!(matches!(
+            parse("msgid \"cat\"\nmsgstr[18446744073709551615] \"Katzen\"\n"),
+            Err(ParseError::InvalidPo(2, _))
+        ));
+    }
+
+    #[test]
+    fn invalid_lines() {
+        assert!(matches!(
+            parse("msgid \"a\"\nmsgstr \"unterminated\n"),
+            Err(ParseError::InvalidPo(2, _))
+        ));
+        assert!(matches!(
+            parse("\"orphan\"\n"),
+            Err(ParseError::InvalidPo(1, _))
+        ));
+        assert!(matches!(
+            parse("msgfoo \"a\"\n"),
+            Err(ParseError::InvalidPo(1, _))
+        ));
+    }
+}
@@ -0,0 +1,7 @@
+//! Locale file parsers of [bevy_simple_i18n](https://docs.rs/bevy_simple_i18n),
+//! shared by its asset loaders, its build script and its CLI, use them through the main crate
+
+pub mod gettext;
+pub mod locale_file;
+pub mod locale_keys;
+pub mod placeholders;
@@ -0,0 +1,159 @@
+// Parsing of the locale file values, shared by the asset loader, the build script and the CLI
+
+use serde_json::Value;
+
+/// CLDR plural categories, used as key suffixes for plural translations
+pub const PLURAL_CATEGORIES: &[&str] = &["zero", "one", "two", "few", "many", "other"];
+
+fn join_keys(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+/// Flattens a `_version: 1` value into dot separated keys, `insert` receives every key and translation
+pub fn flatten_keys(prefix: &str, value: &Value, insert: &mut impl FnMut(String, String)) {
+    let text = match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                flatten_keys(&join_keys(prefix, key), value, insert);
+            }
+            return;
+        }
+        Value::String(text) => text.clone(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::Null | Value::Array(_) => String::new(),
+    };
+    insert(prefix.to_string(), text);
+}
+
+/// Parses a `_version: 2` value, where every key maps locales to their translations,
+/// `insert` receives every locale, key and translation
+pub fn parse_v2(value: &Value, insert: &mut impl FnMut(&str, String, String)) {
+    let Value::Object(messages) = value else {
+        return;
+    };
+    for (key, value) in messages {
+        if let Value::Object(entries) = value {
+            parse_v2_entries(key, entries, insert);
+        }
+    }
+}
+
+/// Entries are either `locale: translation`, `locale: { one: .., other: .. }` plural blocks or nested keys,
+/// `welcome: { title: { en: Welcome } }` is stored as `welcome.title`
+fn parse_v2_entries(
+    key: &str,
+    entries: &serde_json::Map<String, Value>,
+    insert: &mut impl FnMut(&str, String, String),
+) {
+    for (name, value) in entries {
+        match value {
+            Value::String(text) => insert(name, key.to_string(), text.clone()),
+            Value::Object(plurals) if is_plural_block(plurals) => {
+                for (category, text) in plurals {
+                    if let Value::String(text) = text {
+                        insert(name, join_keys(key, category), text.clone());
+                    }
+                }
+            }
+            Value::Object(nested) => parse_v2_entries(&join_keys(key, name), nested, insert),
+            _ => {}
+        }
+    }
+}
+
+/// Returns true if every key of the object is a CLDR plural category with a translation,
+/// `step: { one: { en: .. } }` is a nested key whose keys happen to be category names
+fn is_plural_block(map: &serde_json::Map<String, Value>) -> bool {
+    !map.is_empty()
+        && map
+            .iter()
+            .all(|(key, value)| PLURAL_CATEGORIES.contains(&key.as_str()) && value.is_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn v2_keys(value: &Value) -> Vec<(String, String, String)> {
+        let mut keys = vec![];
+        parse_v2(value, &mut |locale, key, text| {
+            keys.push((locale.to_string(), key, text))
+        });
+        keys.sort();
+        keys
+    }
+
+    fn entry(locale: &str, key: &str, text: &str) -> (String, String, String) {
+        (locale.to_string(), key.to_string(), text.to_string())
+    }
+
+    #[test]
+    fn plural_blocks() {
+        let block = |value: Value| match value {
+            Value::Object(map) => is_plural_block(&map),
+            _ => unreachable!(),
+        };
+        assert!(block(json!({ "one": "cat", "other": "cats" })));
+        assert!(block(json!({ "other": "cats" })));
+        assert!(!block(json!({})));
+        assert!(!block(json!({ "one": "cat", "title": "Cats" })));
+        assert!(!block(json!({ "en": "Cats" })));
+        assert!(!block(
+            json!({ "one": { "en": "Step one" }, "two": { "en": "Step two" } })
+        ));
+    }
+
+    #[test]
+    fn v2_translations() {
+        let value = json!({
+            "_version": 2,
+            "hello": { "en": "Hello", "fr": "Bonjour" },
+            "cats": { "en": { "one": "%{count} cat", "other": "%{count} cats" }, "ja": "%{count}匹" },
+            "welcome": { "title": { "en": "Welcome" } },
+            "tutorial": { "step": { "one": { "en": "Move" }, "two": { "en": "Jump" } } },
+            "found": { "female": { "en": "She found it" }, "other": { "en": "They found it" } },
+        });
+        assert_eq!(
+            v2_keys(&value),
+            [
+                entry("en", "cats.one", "%{count} cat"),
+                entry("en", "cats.other", "%{count} cats"),
+                entry("en", "found.female", "She found it"),
+                entry("en", "found.other", "They found it"),
+                entry("en", "hello", "Hello"),
+                entry("en", "tutorial.step.one", "Move"),
+                entry("en", "tutorial.step.two", "Jump"),
+                entry("en", "welcome.title", "Welcome"),
+                entry("fr", "hello", "Bonjour"),
+                entry("ja", "cats", "%{count}匹"),
+            ]
+        );
+    }
+
+    #[test]
+    fn v1_translations() {
+        let mut keys = vec![];
+        flatten_keys(
+            "",
+            &json!({ "menu": { "start": "Start", "volume": 10, "sound": true }, "messages.hello": "Hello" }),
+            &mut |key, text| keys.push((key, text)),
+        );
+        keys.sort();
+        assert_eq!(
+            keys,
+            [
+                ("menu.sound".to_string(), "true".to_string()),
+                ("menu.start".to_string(), "Start".to_string()),
+                ("menu.volume".to_string(), "10".to_string()),
+                ("messages.hello".to_string(), "Hello".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,188 @@
+// Discovery of the translation keys of the locale files, shared by the build script and the CLI
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+use serde_json::Value;
+
+use crate::gettext::{normalize_locale, parse_mo, parse_po, Catalog};
+
+/// Extensions of the locale files
+pub const TRANSLATION_EXTENSIONS: &[&str] = &["json", "yml", "yaml", "toml", "ftl", "po", "mo"];
+
+/// Translations by locale, then by key, found in the locale files of the asset folder
+pub type LocaleKeys = BTreeMap<String, BTreeMap<String, String>>;
+
+/// Collects the translation keys of a locale file, files that can't be parsed are reported by the asset loaders
+pub fn collect_keys(path: &Path, ext: &str, default_locale: &str, keys: &mut LocaleKeys) {
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+    let mut insert = |locale: &str, key: String, text: String| {
+        keys.entry(locale.to_string())
+            .or_default()
+            .insert(key, text);
+    };
+
+    if ext == "mo" {
+        if let Some(catalog) = fs::read(path).ok().and_then(|bytes| parse_mo(&bytes).ok()) {
+            insert_gettext_messages(&catalog, stem, default_locale, keys);
+        }
+        return;
+    }
+    let Ok(content) = fs::read_to_string(path) else {
+        return;
+    };
+    let value: Value = match ext {
+        "json" => serde_json::from_str(&content).unwrap_or_default(),
+        "yml" | "yaml" => serde_yaml::from_str(&content).unwrap_or_default(),
+        "toml" => toml::from_str(&content).unwrap_or_default(),
+        "ftl" => {
+            // `locales/en/main.ftl` -> `en`
+            let locale = path
+                .parent()
+                .and_then(|parent| parent.file_name())
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+            ftl_keys(&content)
+                .into_iter()
+                .for_each(|(id, text)| insert(locale, id, text));
+            return;
+        }
+        "po" => {
+            if let Ok(catalog) = parse_po(&content) {
+                insert_gettext_messages(&catalog, stem, default_locale, keys);
+            }
+            return;
+        }
+        _ => return,
+    };
+
+    match value.get("_version").and_then(Value::as_u64).unwrap_or(1) {
+        2 => {
+            crate::locale_file::parse_v2(&value, &mut |locale, key, text| insert(locale, key, text))
+        }
+        _ => {
+            // `app.en.json` -> `en`
+            let locale = stem.split('.').next_back().unwrap_or_default();
+            crate::locale_file::flatten_keys("", &value, &mut |key, text| {
+                if key != "_version" {
+                    insert(locale, key, text);
+                }
+            });
+        }
+    }
+}
+
+/// The `msgid`s of gettext catalogs are the source text, so they are known keys of the default locale as well
+///
+/// The locale is read from the `Language` header, falling back to the file name like the asset loader
+fn insert_gettext_messages(
+    catalog: &Catalog,
+    stem: &str,
+    default_locale: &str,
+    keys: &mut LocaleKeys,
+) {
+    let locale = catalog
+        .language()
+        .map_or_else(|| normalize_locale(stem), str::to_string);
+    for (id, text) in catalog.messages() {
+        keys.entry(default_locale.to_string())
+            .or_default()
+            .entry(id.to_string())
+            .or_insert_with(|| id.to_string());
+        keys.entry(locale.clone())
+            .or_default()
+            .insert(id.to_string(), text);
+    }
+}
+
+/// Returns the ids and values of the messages of a Fluent file, terms and attributes can't be translated directly
+fn ftl_keys(content: &str) -> Vec<(String, String)> {
+    let mut messages: Vec<(String, String)> = Vec::new();
+    for line in content.lines() {
+        // Indented lines continue the value of the previous message
+        if line.starts_with(char::is_whitespace) {
+            if let Some((_, text)) = messages.last_mut() {
+                text.push('\n');
+                text.push_str(line.trim());
+            }
+            continue;
+        }
+        let Some((id, text)) = line.split_once('=') else {
+            continue;
+        };
+        let id = id.trim_end();
+        let is_message = id.starts_with(|c: char| c.is_ascii_alphabetic())
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if is_message {
+            messages.push((id.to_string(), text.trim().to_string()));
+        }
+    }
+    messages
+}
+
+/// Returns the key of a translation without its plural form, `messages.cats.one` -> `messages.cats`
+pub fn plural_key(key: &str) -> &str {
+    key.rsplit_once('.')
+        .filter(|(_, category)| crate::locale_file::PLURAL_CATEGORIES.contains(category))
+        .map_or(key, |(parent, _)| parent)
+}
+
+/// Returns the variants of the `Keys` enum generated with the `typed_keys` feature by key,
+/// plural forms are merged into their key, `messages.cats.one` -> `MessagesCats`
+pub fn key_variants<'a>(keys: impl IntoIterator<Item = &'a str>) -> BTreeMap<&'a str, String> {
+    let keys = keys.into_iter().map(plural_key).collect::<BTreeSet<_>>();
+    let mut variants = BTreeSet::new();
+    let mut key_variants = BTreeMap::new();
+    for key in keys {
+        let variant = pascal_case(key);
+        let mut unique = variant.clone();
+        let mut index = 1;
+        while !variants.insert(unique.clone()) {
+            index += 1;
+            unique = format!("{}{}", variant, index);
+        }
+        key_variants.insert(key, unique);
+    }
+    key_variants
+}
+
+/// Converts a translation key to the name of its variant, `messages.hello` -> `MessagesHello`
+fn pascal_case(key: &str) -> String {
+    let mut name: String = key
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name.insert_str(0, "Key");
+    }
+    name
+}
+
+/// Merges the translations of the locale files, the first file with a key of a locale wins
+pub fn merge_keys<'a>(file_keys: impl IntoIterator<Item = &'a LocaleKeys>) -> LocaleKeys {
+    let mut keys = LocaleKeys::new();
+    for locale_keys in file_keys {
+        for (locale, translations) in locale_keys {
+            let merged = keys.entry(locale.clone()).or_default();
+            for (key, text) in translations {
+                merged.entry(key.clone()).or_insert_with(|| text.clone());
+            }
+        }
+    }
+    keys
+}
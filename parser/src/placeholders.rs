@@ -0,0 +1,76 @@
+// Parsing of the placeholders of the translations, used by the build script for the typed keys
+// and the placeholder checks
+
+/// Returns the names of the `%{placeholders}` of a translation and the `{ $variables }` of a Fluent message
+pub fn placeholders(text: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    for start in ["%{", "{ $", "{$"] {
+        let mut rest = text;
+        while let Some(index) = rest.find(start) {
+            rest = &rest[index + start.len()..];
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+                .unwrap_or(rest.len());
+            // `%{name}` must be closed right after the name, Fluent variables can be followed by a selector
+            let closed = start != "%{" || rest[end..].starts_with('}');
+            if end > 0 && closed {
+                names.push(rest[..end].to_string());
+            }
+            rest = &rest[end..];
+        }
+    }
+    names
+}
+
+/// Returns the `%{` of a translation that are not followed by a name and `}`, e.g. `%{name` or `%{ name}`
+pub fn malformed_placeholders(text: &str) -> Vec<String> {
+    text.match_indices("%{")
+        .map(|(index, _)| &text[index..])
+        .filter(|placeholder| {
+            let rest = &placeholder[2..];
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+                .unwrap_or(rest.len());
+            end == 0 || !rest[end..].starts_with('}')
+        })
+        .map(|placeholder| {
+            let placeholder = placeholder.split_inclusive('}').next().unwrap_or_default();
+            let placeholder = placeholder.lines().next().unwrap_or_default();
+            placeholder.chars().take(20).collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_of_translations() {
+        assert_eq!(
+            placeholders("Hello %{name}, you have %{count} %{item-kind}"),
+            ["name", "count", "item-kind"]
+        );
+        assert_eq!(
+            placeholders("{ $count ->\n [one] one cat\n *[other] {$count} cats\n}"),
+            ["count", "count"]
+        );
+        // Unclosed or empty placeholders are malformed, not placeholders
+        assert!(placeholders("%{name %{} 100%").is_empty());
+        assert!(placeholders("No placeholders").is_empty());
+    }
+
+    #[test]
+    fn malformed_placeholders_of_translations() {
+        assert_eq!(
+            malformed_placeholders("Hello %{name, %{ count} and %{}"),
+            ["%{name, %{ count}", "%{ count}", "%{}"]
+        );
+        assert_eq!(malformed_placeholders("%{name\nnext line"), ["%{name"]);
+        assert_eq!(
+            malformed_placeholders("%{a_very_long_placeholder_name"),
+            ["%{a_very_long_placeh"]
+        );
+        assert!(malformed_placeholders("Hello %{name}, 100% { $count }").is_empty());
+    }
+}
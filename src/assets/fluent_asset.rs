@@ -0,0 +1,256 @@
+use std::{path::Path, sync::Arc};
+
+use bevy::{
+    asset::{io::Reader, Asset, AssetLoader, LoadContext},
+    platform::collections::HashMap,
+    reflect::TypePath,
+};
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource};
+use thiserror::Error;
+use unic_langid::LanguageIdentifier;
+
+/// Asset containing a parsed [Project Fluent](https://projectfluent.org) (`.ftl`) file
+///
+/// The locale is taken from the folder the file is in, e.g. `locales/en-US/main.ftl` is loaded for `en-US`
+///
+/// Messages are resolved by their id, attributes by `id.attribute`
+///
+/// # Example
+///
+/// ```ftl
+/// # locales/en/main.ftl
+/// -brand = Bevy
+/// welcome = Welcome to { -brand }, { $name }!
+/// cats = { $count ->
+///     [one] You have one cat
+///    *[other] You have { $count } cats
+/// }
+/// ```
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct FluentAsset {
+    pub(crate) locale: String,
+    pub(crate) resource: Arc<FluentResource>,
+}
+
+impl FluentAsset {
+    /// Returns the locale of this file
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+}
+
+/// Errors that can occur while loading a [FluentAsset]
+#[derive(Debug, Error)]
+pub enum FluentAssetError {
+    #[error("Could not read the Fluent file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Fluent file is not valid UTF-8: {0}")]
+    Utf8(#[from] std::string::FromUtf8Error),
+    #[error("Invalid locale folder for Fluent file: {0}")]
+    InvalidLocale(String),
+}
+
+/// Loads `.ftl` files as [FluentAsset]s
+#[derive(Debug, Default)]
+pub struct FluentAssetLoader;
+
+impl AssetLoader for FluentAssetLoader {
+    type Asset = FluentAsset;
+    type Settings = ();
+    type Error = FluentAssetError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let path = load_context.path();
+        let locale = locale_of_path(path)?;
+
+        bevy::log::debug!("Loading Fluent file: {}", path.display());
+        let resource = match FluentResource::try_new(String::from_utf8(bytes)?) {
+            Ok(resource) => resource,
+            Err((resource, errors)) => {
+                // Fluent recovers from syntax errors, the invalid entries are skipped
+                for error in errors {
+                    bevy::log::warn!("Fluent syntax error in {}: {:?}", path.display(), error);
+                }
+                resource
+            }
+        };
+
+        Ok(FluentAsset {
+            locale,
+            resource: Arc::new(resource),
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ftl"]
+    }
+}
+
+/// Returns the locale of a Fluent file from the folder it is in, `locales/en-US/main.ftl` -> `en-US`
+fn locale_of_path(path: &Path) -> Result<String, FluentAssetError> {
+    let locale = path
+        .parent()
+        .and_then(|parent| parent.file_name())
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    match locale.parse::<LanguageIdentifier>() {
+        Ok(_) => Ok(locale.to_string()),
+        Err(_) => Err(FluentAssetError::InvalidLocale(locale.to_string())),
+    }
+}
+
+/// Fluent bundles by locale, built from every loaded [FluentAsset]
+#[derive(Default)]
+pub(crate) struct FluentBundles(HashMap<String, FluentBundle<Arc<FluentResource>>>);
+
+impl std::fmt::Debug for FluentBundles {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.0.keys()).finish()
+    }
+}
+
+impl FluentBundles {
+    /// Builds the bundles of the assets, the first asset with a message wins
+    pub(crate) fn new<'a>(assets: impl IntoIterator<Item = &'a FluentAsset>) -> Self {
+        let mut bundles = HashMap::<String, FluentBundle<Arc<FluentResource>>>::default();
+        for asset in assets {
+            let bundle = bundles.entry(asset.locale.clone()).or_insert_with(|| {
+                let locale = asset.locale.parse().unwrap_or_default();
+                let mut bundle = FluentBundle::new_concurrent(vec![locale]);
+                // Bevy fonts don't render the Unicode isolation marks around placeables
+                bundle.set_use_isolating(false);
+                bundle
+            });
+            // The messages already in the bundle are kept, the others are added
+            if let Err(errors) = bundle.add_resource(asset.resource.clone()) {
+                bevy::log::debug!(
+                    "Fluent messages of {} already defined by another file: {:?}",
+                    asset.locale,
+                    errors
+                );
+            }
+        }
+        Self(bundles)
+    }
+
+    pub(crate) fn locales(&self) -> impl Iterator<Item = &String> {
+        self.0.keys()
+    }
+
+    /// Formats the message, `id.attribute` keys resolve the attribute of the message
+    pub(crate) fn format(&self, locale: &str, key: &str, args: &FluentArgs) -> Option<String> {
+        let bundle = self.0.get(locale)?;
+        let (id, attribute) = match key.split_once('.') {
+            Some((id, attribute)) => (id, Some(attribute)),
+            None => (key, None),
+        };
+        let message = bundle.get_message(id)?;
+        let pattern = match attribute {
+            Some(attribute) => message.get_attribute(attribute)?.value(),
+            None => message.value()?,
+        };
+
+        let mut errors = vec![];
+        let formatted = bundle.format_pattern(pattern, Some(args), &mut errors);
+        for error in errors {
+            bevy::log::warn!(
+                "Fluent error for key: {} in locale: {}: {}",
+                key,
+                locale,
+                error
+            );
+        }
+        Some(formatted.into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluent_bundle::FluentValue;
+
+    use super::*;
+
+    fn asset(locale: &str, content: &str) -> FluentAsset {
+        FluentAsset {
+            locale: locale.to_string(),
+            resource: Arc::new(FluentResource::try_new(content.to_string()).unwrap()),
+        }
+    }
+
+    const MAIN: &str = r#"
+-brand = Bevy
+welcome = Welcome to { -brand }, { $name }!
+cats = { $count ->
+    [one] You have one cat
+   *[other] You have { $count } cats
+}
+menu = Menu
+    .title = Main menu
+"#;
+
+    #[test]
+    fn locale_of_the_parent_folder() {
+        assert_eq!(
+            locale_of_path(Path::new("locales/en-US/main.ftl")).unwrap(),
+            "en-US"
+        );
+        assert!(matches!(
+            locale_of_path(Path::new("locales/not a locale/main.ftl")),
+            Err(FluentAssetError::InvalidLocale(_))
+        ));
+        assert!(matches!(
+            locale_of_path(Path::new("main.ftl")),
+            Err(FluentAssetError::InvalidLocale(_))
+        ));
+    }
+
+    #[test]
+    fn messages_and_attributes() {
+        let bundles = FluentBundles::new([&asset("en", MAIN)]);
+        let mut args = FluentArgs::new();
+        args.set("name", "Alice");
+
+        assert_eq!(
+            bundles.format("en", "welcome", &args).as_deref(),
+            Some("Welcome to Bevy, Alice!")
+        );
+        assert_eq!(
+            bundles.format("en", "menu.title", &args).as_deref(),
+            Some("Main menu")
+        );
+        assert_eq!(bundles.format("en", "menu.missing", &args), None);
+        assert_eq!(bundles.format("en", "-brand", &args), None);
+        assert_eq!(bundles.format("fr", "menu", &args), None);
+    }
+
+    #[test]
+    fn number_selectors() {
+        let bundles = FluentBundles::new([&asset("en", MAIN)]);
+        let cats = |count: &str| {
+            let mut args = FluentArgs::new();
+            args.set("count", FluentValue::try_number(count));
+            bundles.format("en", "cats", &args)
+        };
+        assert_eq!(cats("1").as_deref(), Some("You have one cat"));
+        assert_eq!(cats("3").as_deref(), Some("You have 3 cats"));
+    }
+
+    #[test]
+    fn first_file_with_a_message_wins() {
+        let first = asset("en", "menu = Menu\n");
+        let second = asset("en", "menu = Other menu\nquit = Quit\n");
+        let bundles = FluentBundles::new([&first, &second]);
+        let args = FluentArgs::new();
+
+        assert_eq!(bundles.format("en", "menu", &args).as_deref(), Some("Menu"));
+        assert_eq!(bundles.format("en", "quit", &args).as_deref(), Some("Quit"));
+    }
+}
@@ -0,0 +1,240 @@
+use bevy::{
+    asset::{io::Reader, Asset, AssetLoader, LoadContext},
+    platform::collections::HashMap,
+    reflect::TypePath,
+};
+use bevy_simple_i18n_parser::gettext::{self, Catalog, ParseError, PluralForms};
+use icu_locid::LanguageIdentifier;
+use thiserror::Error;
+
+/// Asset containing a gettext catalog, loaded from a `.po` file or a compiled `.mo` file
+///
+/// The locale is read from the `Language` header of the catalog,
+/// falling back to the file name, e.g. `locales/de.po`
+///
+/// Messages are resolved by their `msgid`, [I18nText::with_context](crate::prelude::I18nText::with_context)
+/// selects the `msgctxt` and plural forms are selected with the `Plural-Forms` header of the catalog
+/// from the `count` number argument
+///
+/// # Example
+///
+/// ```po
+/// msgid ""
+/// msgstr ""
+/// "Language: de\n"
+/// "Plural-Forms: nplurals=2; plural=(n != 1);\n"
+///
+/// msgctxt "menu"
+/// msgid "Open"
+/// msgstr "Öffnen"
+///
+/// msgid "You have %{count} cat"
+/// msgid_plural "You have %{count} cats"
+/// msgstr[0] "Du hast %{count} Katze"
+/// msgstr[1] "Du hast %{count} Katzen"
+/// ```
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct GettextAsset {
+    pub(crate) locale: String,
+    pub(crate) catalog: Catalog,
+}
+
+impl GettextAsset {
+    /// Returns the locale of this catalog
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+}
+
+/// Errors that can occur while loading a [GettextAsset]
+#[derive(Debug, Error)]
+pub enum GettextAssetError {
+    #[error("Could not read the gettext catalog: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Gettext catalog is not valid UTF-8: {0}")]
+    Utf8(#[from] std::str::Utf8Error),
+    #[error("Invalid PO file at line {0}: {1}")]
+    InvalidPo(usize, String),
+    #[error("Invalid MO file: {0}")]
+    InvalidMo(&'static str),
+    #[error("Could not find the locale of the gettext catalog, add a `Language` header")]
+    MissingLocale,
+}
+
+impl From<ParseError> for GettextAssetError {
+    fn from(err: ParseError) -> Self {
+        match err {
+            ParseError::InvalidPo(line, content) => Self::InvalidPo(line, content),
+            ParseError::InvalidMo(reason) => Self::InvalidMo(reason),
+        }
+    }
+}
+
+/// Loads `.po` and `.mo` files as [GettextAsset]s
+#[derive(Debug, Default)]
+pub struct GettextAssetLoader;
+
+impl AssetLoader for GettextAssetLoader {
+    type Asset = GettextAsset;
+    type Settings = ();
+    type Error = GettextAssetError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let path = load_context.path();
+        bevy::log::debug!("Loading gettext catalog: {}", path.display());
+        let catalog = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("mo") => gettext::parse_mo(&bytes)?,
+            _ => gettext::parse_po(std::str::from_utf8(&bytes)?)?,
+        };
+        if let Some(plural_forms) = catalog.invalid_plural_forms() {
+            bevy::log::warn!("Invalid Plural-Forms header: {}", plural_forms);
+        }
+
+        let locale = catalog
+            .language()
+            .and_then(normalize_locale)
+            .or_else(|| {
+                path.file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(normalize_locale)
+            })
+            .ok_or(GettextAssetError::MissingLocale)?;
+
+        Ok(GettextAsset { locale, catalog })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["po", "mo"]
+    }
+}
+
+/// Converts gettext locales to BCP-47, `pt_BR` -> `pt-BR`, returns `None` if it isn't a valid locale
+fn normalize_locale(locale: &str) -> Option<String> {
+    gettext::normalize_locale(locale)
+        .parse::<LanguageIdentifier>()
+        .ok()
+        .map(|locale| locale.to_string())
+}
+
+/// Gettext catalogs by locale, from every loaded [GettextAsset]
+///
+/// The catalogs of a locale are kept in order, the first one with a message wins
+/// and its own `Plural-Forms` select the plural form
+#[derive(Debug, Default)]
+pub(crate) struct GettextCatalogs(HashMap<String, Vec<Catalog>>);
+
+impl GettextCatalogs {
+    pub(crate) fn new<'a>(assets: impl IntoIterator<Item = &'a GettextAsset>) -> Self {
+        let mut catalogs = HashMap::<String, Vec<Catalog>>::default();
+        for asset in assets {
+            catalogs
+                .entry(asset.locale.clone())
+                .or_default()
+                .push(asset.catalog.clone());
+        }
+        Self(catalogs)
+    }
+
+    pub(crate) fn locales(&self) -> impl Iterator<Item = &String> {
+        self.0.keys()
+    }
+
+    /// Returns the translation of the message, `count` selects the plural form of plural messages
+    pub(crate) fn get(
+        &self,
+        locale: &str,
+        context: Option<&str>,
+        id: &str,
+        count: Option<u64>,
+    ) -> Option<&str> {
+        let (catalog, strs) = self
+            .0
+            .get(locale)?
+            .iter()
+            .find_map(|catalog| Some((catalog, catalog.get(context, id)?)))?;
+        let index = match (strs.len(), count) {
+            (0 | 1, _) | (_, None) => 0,
+            (_, Some(count)) => match catalog.plural_forms() {
+                Some(plural_forms) => plural_forms.index(count),
+                None => PluralForms::default().index(count),
+            },
+        };
+        strs.get(index)
+            .or_else(|| strs.first())
+            .map(String::as_str)
+            .filter(|translation| !translation.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(content: &str) -> GettextAsset {
+        GettextAsset {
+            locale: "de".to_string(),
+            catalog: gettext::parse_po(content).unwrap(),
+        }
+    }
+
+    #[test]
+    fn first_catalog_with_a_message_wins() {
+        let first = asset(
+            r#"
+msgid ""
+msgstr ""
+"Plural-Forms: nplurals=2; plural=(n != 1);\n"
+
+msgid "Hello"
+msgstr "Hallo"
+
+msgid "cat"
+msgid_plural "cats"
+msgstr[0] "Katze"
+msgstr[1] "Katzen"
+"#,
+        );
+        let second = asset(
+            r#"
+msgid ""
+msgstr ""
+"Plural-Forms: nplurals=3; plural=(n==1 ? 0 : n==2 ? 1 : 2);\n"
+
+msgid "Hello"
+msgstr "Servus"
+
+msgid "cat"
+msgid_plural "cats"
+msgstr[0] "eine Katze"
+msgstr[1] "zwei Katzen"
+msgstr[2] "viele Katzen"
+
+msgid "dog"
+msgid_plural "dogs"
+msgstr[0] "ein Hund"
+msgstr[1] "zwei Hunde"
+msgstr[2] "viele Hunde"
+"#,
+        );
+        let catalogs = GettextCatalogs::new([&first, &second]);
+
+        assert_eq!(catalogs.get("de", None, "Hello", None), Some("Hallo"));
+        assert_eq!(catalogs.get("de", None, "cat", Some(5)), Some("Katzen"));
+        // Messages are paired with the plural forms of their own catalog
+        assert_eq!(catalogs.get("de", None, "dog", Some(2)), Some("zwei Hunde"));
+        assert_eq!(
+            catalogs.get("de", None, "dog", Some(5)),
+            Some("viele Hunde")
+        );
+        assert_eq!(catalogs.get("de", None, "bird", None), None);
+        assert_eq!(catalogs.get("fr", None, "Hello", None), None);
+    }
+}
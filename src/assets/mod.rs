@@ -0,0 +1,11 @@
+#[cfg(feature = "fluent")]
+mod fluent_asset;
+#[cfg(feature = "gettext")]
+mod gettext;
+mod translation_asset;
+
+#[cfg(feature = "fluent")]
+pub use fluent_asset::*;
+#[cfg(feature = "gettext")]
+pub use gettext::*;
+pub use translation_asset::*;
@@ -0,0 +1,154 @@
+use bevy::{
+    asset::{io::Reader, Asset, AssetLoader, LoadContext},
+    platform::collections::HashMap,
+    reflect::TypePath,
+};
+use bevy_simple_i18n_parser::locale_file::{flatten_keys, parse_v2};
+use serde_json::Value;
+use thiserror::Error;
+
+/// Asset containing the translations of a single locale file
+///
+/// The supported layouts are the same as the ones used by `rust-i18n`:
+///
+/// - `_version: 1` (default): the locale is taken from the file name, e.g. `en.json` or `app.en.yml`
+/// - `_version: 2`: every key maps locales to their translations, so a single file can hold many locales
+///
+/// Nested objects are flattened into dot separated keys, e.g. `messages.hello`
+///
+/// Plural translations use the CLDR plural categories as the last part of the key, e.g. `messages.cats.one`,
+/// in `_version: 2` files they can also be written as a block of categories under the locale
+///
+/// # Example
+///
+/// ```yml
+/// # v1, en.yml
+/// hello: Hello world
+/// messages:
+///   hello: Hello, %{name}
+/// ```
+///
+/// ```yml
+/// # v2, any_name.yml
+/// _version: 2
+/// hello:
+///   en: Hello world
+///   ja: こんにちは世界
+/// cats:
+///   en:
+///     one: You have %{count} cat
+///     other: You have %{count} cats
+///   ja: 猫を%{count}匹飼っています
+/// ```
+#[derive(Asset, TypePath, Debug, Default, Clone)]
+pub struct TranslationAsset {
+    /// Translations by locale, then by key
+    pub(crate) locales: HashMap<String, HashMap<String, String>>,
+}
+
+impl TranslationAsset {
+    /// Returns the translation of the key for the provided locale, if this file contains it
+    pub fn get(&self, locale: &str, key: &str) -> Option<&str> {
+        self.locales
+            .get(locale)
+            .and_then(|translations| translations.get(key))
+            .map(String::as_str)
+    }
+
+    /// Returns the locales found in this file
+    pub fn locales(&self) -> impl Iterator<Item = &str> {
+        self.locales.keys().map(String::as_str)
+    }
+
+    /// Parses a locale file, `ext` selects the format and `locale` is used for `_version: 1` files
+    pub fn parse(content: &str, ext: &str, locale: &str) -> Result<Self, TranslationAssetError> {
+        let value: Value = match ext {
+            "json" => serde_json::from_str(content)?,
+            "yml" | "yaml" => serde_yaml::from_str(content)?,
+            "toml" => toml::from_str(content)?,
+            _ => return Err(TranslationAssetError::UnsupportedExtension(ext.to_string())),
+        };
+
+        let mut asset = Self::default();
+        match value.get("_version").and_then(Value::as_u64).unwrap_or(1) {
+            2 => {
+                parse_v2(&value, &mut |locale, key, text| {
+                    asset
+                        .locales
+                        .entry(locale.to_string())
+                        .or_default()
+                        .insert(key, text);
+                });
+                if asset.locales.is_empty() {
+                    return Err(TranslationAssetError::InvalidFormat);
+                }
+            }
+            _ => {
+                let translations = asset.locales.entry(locale.to_string()).or_default();
+                flatten_keys("", &value, &mut |key, text| {
+                    translations.insert(key, text);
+                });
+                translations.remove("_version");
+            }
+        }
+        Ok(asset)
+    }
+}
+
+/// Errors that can occur while loading a [TranslationAsset]
+#[derive(Debug, Error)]
+pub enum TranslationAssetError {
+    #[error("Could not read the locale file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Locale file is not valid UTF-8: {0}")]
+    Utf8(#[from] std::str::Utf8Error),
+    #[error("Invalid JSON format: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid YAML format: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("Invalid TOML format: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("Unsupported locale file extension: {0}")]
+    UnsupportedExtension(String),
+    #[error("Invalid locale file format, please check the version field")]
+    InvalidFormat,
+}
+
+/// Loads `.json`, `.yml`, `.yaml` and `.toml` locale files as [TranslationAsset]s
+#[derive(Debug, Default)]
+pub struct TranslationAssetLoader;
+
+impl AssetLoader for TranslationAssetLoader {
+    type Asset = TranslationAsset;
+    type Settings = ();
+    type Error = TranslationAssetError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let path = load_context.path();
+        let ext = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default();
+        // `app.en.json` -> `en`
+        let locale = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.split('.').next_back())
+            .unwrap_or_default();
+
+        bevy::log::debug!("Loading locale file: {}", path.display());
+        TranslationAsset::parse(std::str::from_utf8(&bytes)?, ext, locale)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["json", "yml", "yaml", "toml"]
+    }
+}
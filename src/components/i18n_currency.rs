@@ -0,0 +1,422 @@
+use bevy::{
+    ecs::{
+        component::{Component, ComponentHook, HookContext, Mutable, StorageType},
+        reflect::ReflectComponent,
+    },
+    log::debug,
+    reflect::Reflect,
+    ui::widget::Text,
+};
+use fixed_decimal::{FixedDecimal, Sign};
+use icu_experimental::dimension::provider::currency::{
+    Baked, CurrencyEssentialsV1Marker, PatternSelection, PlaceholderValue,
+};
+use icu_provider::{DataProvider, DataRequest};
+use thiserror::Error;
+use tinystr::TinyAsciiStr;
+
+use crate::resources::I18n;
+
+use super::{utils, I18nComponent, I18nNumber, InterpolationType};
+
+/// Key of the currency names in the locale files, by ISO 4217 code, e.g. `currency.USD`
+const CURRENCY_KEY: &str = "currency";
+
+/// Currencies that don't use 2 minor unit digits, from the `<fractions>` of the CLDR supplemental data
+///
+/// ICU4X 1.5 doesn't bake the CLDR currency data, so this mirrors its `digits` attribute
+const MINOR_UNITS: &[(&str, i16)] = &[
+    ("ADP", 0),
+    ("AFN", 0),
+    ("ALL", 0),
+    ("BHD", 3),
+    ("BIF", 0),
+    ("BYR", 0),
+    ("CLF", 4),
+    ("CLP", 0),
+    ("DJF", 0),
+    ("ESP", 0),
+    ("GNF", 0),
+    ("IQD", 0),
+    ("IRR", 0),
+    ("ISK", 0),
+    ("ITL", 0),
+    ("JOD", 3),
+    ("JPY", 0),
+    ("KMF", 0),
+    ("KPW", 0),
+    ("KRW", 0),
+    ("KWD", 3),
+    ("LAK", 0),
+    ("LBP", 0),
+    ("LUF", 0),
+    ("LYD", 3),
+    ("MGA", 0),
+    ("MGF", 0),
+    ("MMK", 0),
+    ("MRO", 0),
+    ("OMR", 3),
+    ("PYG", 0),
+    ("RSD", 0),
+    ("RWF", 0),
+    ("SLL", 0),
+    ("SOS", 0),
+    ("STD", 0),
+    ("SYP", 0),
+    ("TMM", 0),
+    ("TND", 3),
+    ("TRL", 0),
+    ("UGX", 0),
+    ("UYI", 0),
+    ("UYW", 4),
+    ("VND", 0),
+    ("VUV", 0),
+    ("XAF", 0),
+    ("XOF", 0),
+    ("XPF", 0),
+    ("YER", 0),
+    ("ZMK", 0),
+    ("ZWD", 0),
+];
+
+/// ISO 4217 code of the transactions without a currency, used by [I18nCurrency::default]
+const NO_CURRENCY: &str = "XXX";
+
+/// Error returned by [I18nCurrency::try_new]
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum InvalidCurrency {
+    /// The currency code is not 3 ASCII letters
+    #[error("Invalid ISO 4217 currency code: {0}")]
+    Code(String),
+    /// The amount is NaN or infinite
+    #[error("Invalid currency amount: {0}")]
+    Amount(f64),
+}
+
+/// How the currency is displayed next to the amount
+#[derive(Default, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurrencyDisplay {
+    /// Symbol of the currency in the locale, `$24,501.20`, `US$24,501.20`
+    #[default]
+    Symbol,
+    /// Shortest symbol of the currency, `$24,501.20`
+    NarrowSymbol,
+    /// ISO 4217 code, `USD 24,501.20`
+    Code,
+    /// Name of the currency from the `currency.{code}` translations of the locale, `24,501.20 US dollars`,
+    /// falls back to the code if the locale has no name for the currency
+    Name,
+}
+
+/// Component for spawning localized currency entities that are managed by `bevy_simple_i18n`
+///
+/// It automatically inserts (or replaces) a Bevy `Text` component with the localized amount
+///
+/// The amount is rounded to the minor unit digits of the currency, e.g. 2 for `USD` and 0 for `JPY`
+///
+/// Updates automatically whenever the locale is changed using the [crate::resources::I18n] resource
+///
+/// # Example
+///
+/// ```yml
+/// # Currency names, used with `CurrencyDisplay::Name`
+/// currency.USD:
+///   en:
+///     one: '%{amount} US dollar'
+///     other: '%{amount} US dollars'
+/// ```
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// # let mut world = World::new();
+/// // Basic usage, `$24,501.20` in `en`, `24.501,20 $` in `de`
+/// world.spawn(I18nCurrency::new(24501.2, "USD"));
+///
+/// // With the ISO code, `EUR 24,501.20`
+/// world.spawn(I18nCurrency::new(24501.2, "EUR").with_display(CurrencyDisplay::Code));
+///
+/// // With forced locale
+/// // overrides the global
+/// // does not update when the locale is changed
+/// world.spawn(I18nCurrency::new(24501.2, "JPY").with_locale("ja"));
+///
+/// // With a code entered by the player
+/// if let Ok(currency) = I18nCurrency::try_new(24501.2, "usd") {
+///     world.spawn(currency);
+/// }
+/// ```
+#[derive(Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct I18nCurrency {
+    #[reflect(ignore)]
+    pub(crate) fixed_decimal: FixedDecimal,
+    /// ISO 4217 currency code, e.g. `USD`
+    code: String,
+    /// How the currency is displayed
+    display: CurrencyDisplay,
+    /// Locale for this specific translation, `None` to use the global locale
+    pub(crate) locale: Option<String>,
+}
+
+impl I18nComponent for I18nCurrency {
+    fn locale(&self) -> String {
+        self.locale
+            .clone()
+            .unwrap_or(rust_i18n::locale().to_string())
+    }
+
+    fn translate(&self, i18n: &I18n) -> String {
+        self.format(i18n, &self.locale())
+    }
+}
+
+impl I18nCurrency {
+    /// Creates a new `I18nCurrency` component with the provided amount and ISO 4217 currency code
+    ///
+    /// Panics if the code is not 3 ASCII letters or the amount is not finite, see [I18nCurrency::try_new] for codes that aren't known at compile time
+    pub fn new(amount: impl Into<f64>, code: impl Into<String>) -> Self {
+        Self::try_new(amount, code).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates a new `I18nCurrency` component with the provided amount and ISO 4217 currency code,
+    /// or returns an error if the code is not 3 ASCII letters or the amount is NaN or infinite
+    pub fn try_new(
+        amount: impl Into<f64>,
+        code: impl Into<String>,
+    ) -> Result<Self, InvalidCurrency> {
+        let code = code.into().to_ascii_uppercase();
+        if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(InvalidCurrency::Code(code));
+        }
+        let amount = amount.into();
+        if !amount.is_finite() {
+            return Err(InvalidCurrency::Amount(amount));
+        }
+        let mut fixed_decimal = utils::f64_to_fd(amount);
+        let digits = minor_unit_digits(&code);
+        fixed_decimal.half_even(-digits);
+        fixed_decimal.pad_end(-digits);
+        Ok(Self {
+            fixed_decimal,
+            code,
+            display: CurrencyDisplay::default(),
+            locale: None,
+        })
+    }
+
+    /// Set how the currency is displayed next to the amount
+    pub fn with_display(mut self, display: CurrencyDisplay) -> Self {
+        self.display = display;
+        self
+    }
+
+    /// Set the locale for this specific translation
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Formats the amount with the CLDR currency patterns of the locale
+    pub(crate) fn format(&self, i18n: &I18n, locale: &str) -> String {
+        if self.display == CurrencyDisplay::Name {
+            let key = format!("{}.{}", CURRENCY_KEY, self.code);
+            let args = [(
+                "amount".to_string(),
+                InterpolationType::Number(I18nNumber::from_fixed_decimal(
+                    self.fixed_decimal.clone(),
+                )),
+            )];
+            let translated = utils::translate_by_key(i18n, locale, &key, None, &args);
+            if translated != key {
+                return translated;
+            }
+        }
+
+        // The currency patterns don't include the sign yet, so it is placed before the whole amount
+        let mut amount = self.fixed_decimal.clone();
+        let negative = amount.sign() == Sign::Negative;
+        amount.set_sign(Sign::None);
+        let cldr_locale = utils::cldr_locale(i18n, locale);
+        let formatted = utils::get_formatter(&cldr_locale, &self.code).format_to_string(&amount);
+        let sign = if negative { "-" } else { "" };
+
+        let essentials = DataProvider::<CurrencyEssentialsV1Marker>::load(
+            &Baked,
+            DataRequest {
+                locale: &utils::resolve_locale(&cldr_locale, &self.code).into(),
+                metadata: Default::default(),
+            },
+        )
+        .and_then(|response| response.take_payload());
+        let Ok(essentials) = essentials else {
+            debug!("Missing currency patterns for locale: {}", cldr_locale);
+            return format!("{}{} {}", sign, self.code, formatted);
+        };
+        // The code can still be replaced through reflection
+        let Ok(code) = TinyAsciiStr::<3>::from_str(&self.code) else {
+            return format!("{}{} {}", sign, self.code, formatted);
+        };
+        let essentials = essentials.get();
+
+        let config = essentials
+            .pattern_config_map
+            .get_copied(&code.to_unvalidated())
+            .unwrap_or(essentials.default_pattern_config);
+        let (placeholder, selection) = match self.display {
+            CurrencyDisplay::Symbol => (
+                config.short_placeholder_value,
+                config.short_pattern_selection,
+            ),
+            CurrencyDisplay::NarrowSymbol => (
+                config.narrow_placeholder_value,
+                config.narrow_pattern_selection,
+            ),
+            CurrencyDisplay::Code | CurrencyDisplay::Name => (
+                Some(PlaceholderValue::ISO),
+                PatternSelection::StandardAlphaNextToNumber,
+            ),
+        };
+        let symbol = match placeholder {
+            Some(PlaceholderValue::Index(index)) => essentials
+                .placeholders
+                .get(index.into())
+                .unwrap_or(&self.code),
+            Some(PlaceholderValue::ISO) | None => &self.code,
+        };
+        let pattern = match selection {
+            PatternSelection::Standard => essentials.standard_pattern.as_ref(),
+            PatternSelection::StandardAlphaNextToNumber => essentials
+                .standard_alpha_next_to_number_pattern
+                .as_ref()
+                .or(essentials.standard_pattern.as_ref()),
+        };
+        let Some(pattern) = pattern else {
+            return format!("{}{} {}", sign, symbol, formatted);
+        };
+        let mut result = pattern
+            .interpolate((formatted.as_str(), symbol))
+            .to_string();
+        // CLDR currency spacing, letters of the symbol are never attached to the digits, `JPY 24,501`
+        if symbol.ends_with(char::is_alphabetic) {
+            let attached = format!("{}{}", symbol, formatted);
+            result = result.replace(&attached, &format!("{}\u{a0}{}", symbol, formatted));
+        }
+        if symbol.starts_with(char::is_alphabetic) {
+            let attached = format!("{}{}", formatted, symbol);
+            result = result.replace(&attached, &format!("{}\u{a0}{}", formatted, symbol));
+        }
+        format!("{}{}", sign, result)
+    }
+}
+
+impl Default for I18nCurrency {
+    fn default() -> Self {
+        Self::new(0, NO_CURRENCY)
+    }
+}
+
+/// Returns the number of minor unit digits of the ISO 4217 currency, 2 for most currencies
+fn minor_unit_digits(code: &str) -> i16 {
+    MINOR_UNITS
+        .iter()
+        .find(|(currency, _)| *currency == code)
+        .map_or(2, |(_, digits)| *digits)
+}
+
+impl Component for I18nCurrency {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = Mutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world, HookContext { entity, .. }| {
+            let val = world.get::<Self>(entity).unwrap().clone();
+            let translated = val.translate(world.resource::<I18n>());
+            debug!("Adding i18n currency: {} {}", val.fixed_decimal, val.code);
+            if let Some(mut text) = world.get_mut::<Text>(entity) {
+                **text = translated;
+            } else {
+                world
+                    .commands()
+                    .entity(entity)
+                    .insert(Text::new(translated));
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(currency: I18nCurrency, locale: &str) -> String {
+        currency.format(&I18n::default(), locale)
+    }
+
+    #[test]
+    fn yen_has_no_minor_units() {
+        assert_eq!(format(I18nCurrency::new(24501.5, "JPY"), "en"), "¥24,502");
+        assert_eq!(format(I18nCurrency::new(24501.2, "JPY"), "ja"), "￥24,501");
+    }
+
+    #[test]
+    fn euro_in_german() {
+        assert_eq!(
+            format(I18nCurrency::new(24501.2, "EUR"), "de"),
+            "24.501,20\u{a0}€"
+        );
+    }
+
+    #[test]
+    fn negative_amount() {
+        assert_eq!(format(I18nCurrency::new(-5.5, "USD"), "en"), "-$5.50");
+    }
+
+    #[test]
+    fn iso_code() {
+        let currency = I18nCurrency::new(24501.2, "EUR").with_display(CurrencyDisplay::Code);
+        assert_eq!(format(currency, "en"), "EUR\u{a0}24,501.20");
+    }
+
+    #[test]
+    fn minor_units_follow_cldr() {
+        assert_eq!(
+            format(I18nCurrency::new(1.2345, "KWD"), "en"),
+            "KWD\u{a0}1.234"
+        );
+        assert_eq!(minor_unit_digits("IQD"), 0);
+        assert_eq!(minor_unit_digits("USD"), 2);
+    }
+
+    #[test]
+    fn invalid_codes() {
+        assert_eq!(
+            I18nCurrency::try_new(1, "dollar").unwrap_err(),
+            InvalidCurrency::Code("DOLLAR".to_string())
+        );
+        assert!(I18nCurrency::try_new(1, "").is_err());
+        assert_eq!(I18nCurrency::try_new(1, "usd").unwrap().code, "USD");
+    }
+
+    #[test]
+    fn invalid_amounts() {
+        assert_eq!(
+            I18nCurrency::try_new(f64::INFINITY, "usd").unwrap_err(),
+            InvalidCurrency::Amount(f64::INFINITY)
+        );
+        assert!(I18nCurrency::try_new(f64::NAN, "usd").is_err());
+        assert!(I18nCurrency::try_new(f64::MAX, "usd").is_ok());
+    }
+
+    #[test]
+    fn default_formats_without_a_currency() {
+        assert_eq!(format(I18nCurrency::default(), "en"), "¤0.00");
+    }
+
+    #[test]
+    fn invalid_reflected_code_falls_back_to_the_code() {
+        let mut currency = I18nCurrency::new(-3, "USD");
+        currency.code = "DOLLAR".to_string();
+        assert_eq!(format(currency, "en"), "-DOLLAR 3.00");
+    }
+}
@@ -0,0 +1,417 @@
+use bevy::{
+    ecs::{
+        component::{Component, ComponentHook, HookContext, Mutable, StorageType},
+        reflect::ReflectComponent,
+    },
+    log::{debug, warn},
+    reflect::Reflect,
+    ui::widget::Text,
+};
+use icu_calendar::DateTime;
+use icu_datetime::options::length;
+use thiserror::Error;
+
+use crate::resources::I18n;
+
+use super::{utils, I18nComponent};
+
+/// Length of a formatted date or time, following the CLDR date and time formats of the locale
+#[derive(Default, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateTimeLength {
+    /// `1/2/25`, `3:04 PM`
+    Short,
+    /// `Jan 2, 2025`, `3:04:05 PM`
+    #[default]
+    Medium,
+    /// `January 2, 2025`, times have no long format without a time zone so they are formatted as medium
+    Long,
+}
+
+/// Error returned by the fallible constructors of [I18nDateTime] when the date or time doesn't exist
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("Invalid date and time: {0}")]
+pub struct InvalidDateTime(pub String);
+
+/// Component for spawning localized date and time entities that are managed by `bevy_simple_i18n`
+///
+/// It automatically inserts (or replaces) a Bevy `Text` component with the localized date and time
+///
+/// Updates automatically whenever the locale is changed using the [crate::resources::I18n] resource
+///
+/// # Example
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// # let mut world = World::new();
+/// // Date only, `Jan 2, 2025`
+/// world.spawn(I18nDateTime::new(2025, 1, 2));
+///
+/// // Date and time, `1/2/25, 3:04 PM`
+/// world.spawn(
+///     I18nDateTime::new(2025, 1, 2)
+///         .with_time(15, 4, 5)
+///         .with_date_length(DateTimeLength::Short),
+/// );
+///
+/// // From a UTC unix timestamp
+/// world.spawn(I18nDateTime::from_unix_timestamp(1_735_830_245));
+///
+/// // With forced locale
+/// // overrides the global
+/// // does not update when the locale is changed
+/// world.spawn(I18nDateTime::new(2025, 1, 2).with_locale("ja"));
+///
+/// // With a date that isn't known at compile time
+/// if let Ok(date) = I18nDateTime::try_new(2025, 2, 29) {
+///     world.spawn(date);
+/// }
+/// ```
+#[derive(Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct I18nDateTime {
+    /// Date as `(year, month, day)`
+    date: (i32, u8, u8),
+    /// Time as `(hour, minute, second)`
+    time: (u8, u8, u8),
+    /// Length of the date, `None` to hide the date
+    date_length: Option<DateTimeLength>,
+    /// Length of the time, `None` to hide the time
+    time_length: Option<DateTimeLength>,
+    /// Locale for this specific translation, `None` to use the global locale
+    pub(crate) locale: Option<String>,
+}
+
+impl I18nComponent for I18nDateTime {
+    fn locale(&self) -> String {
+        self.locale
+            .clone()
+            .unwrap_or(rust_i18n::locale().to_string())
+    }
+
+    fn translate(&self, i18n: &I18n) -> String {
+        self.format(i18n, &self.locale())
+    }
+}
+
+impl I18nDateTime {
+    /// Creates a new `I18nDateTime` component with the provided ISO date, without a time
+    ///
+    /// Panics if the date is invalid, see [I18nDateTime::try_new] for dates that aren't known at compile time
+    pub fn new(year: i32, month: u8, day: u8) -> Self {
+        Self::try_new(year, month, day).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates a new `I18nDateTime` component with the provided ISO date, without a time,
+    /// or an error if the date doesn't exist
+    pub fn try_new(year: i32, month: u8, day: u8) -> Result<Self, InvalidDateTime> {
+        let date = Self {
+            date: (year, month, day),
+            time: (0, 0, 0),
+            date_length: Some(DateTimeLength::Medium),
+            time_length: None,
+            locale: None,
+        };
+        date.to_datetime()?;
+        Ok(date)
+    }
+
+    /// Creates a new `I18nDateTime` component with the date and time of a UTC unix timestamp in seconds
+    ///
+    /// Panics if the year doesn't fit in an `i32`, see [I18nDateTime::try_from_unix_timestamp]
+    pub fn from_unix_timestamp(seconds: i64) -> Self {
+        Self::try_from_unix_timestamp(seconds).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates a new `I18nDateTime` component with the date and time of a UTC unix timestamp in seconds,
+    /// or an error if the year doesn't fit in an `i32`
+    pub fn try_from_unix_timestamp(timestamp: i64) -> Result<Self, InvalidDateTime> {
+        let (days, seconds) = (timestamp.div_euclid(86_400), timestamp.rem_euclid(86_400));
+        let (year, month, day) = civil_from_days(days)
+            .ok_or_else(|| InvalidDateTime(format!("unix timestamp {}", timestamp)))?;
+        Self::try_new(year, month, day)?.try_with_time(
+            (seconds / 3600) as u8,
+            (seconds % 3600 / 60) as u8,
+            (seconds % 60) as u8,
+        )
+    }
+
+    /// Set the time, which is shown with the short length unless another length was set
+    ///
+    /// Panics if the time is invalid, see [I18nDateTime::try_with_time] for times that aren't known at compile time
+    pub fn with_time(self, hour: u8, minute: u8, second: u8) -> Self {
+        self.try_with_time(hour, minute, second)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Set the time, which is shown with the short length unless another length was set,
+    /// or an error if the time doesn't exist
+    pub fn try_with_time(
+        mut self,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Result<Self, InvalidDateTime> {
+        self.time = (hour, minute, second);
+        self.time_length = self.time_length.or(Some(DateTimeLength::Short));
+        self.to_datetime()?;
+        Ok(self)
+    }
+
+    /// Set the length of the date
+    pub fn with_date_length(mut self, length: DateTimeLength) -> Self {
+        self.date_length = Some(length);
+        self
+    }
+
+    /// Set the length of the time
+    pub fn with_time_length(mut self, length: DateTimeLength) -> Self {
+        self.time_length = Some(length);
+        self
+    }
+
+    /// Only show the time
+    pub fn without_date(mut self) -> Self {
+        self.date_length = None;
+        self.time_length = self.time_length.or(Some(DateTimeLength::Short));
+        self
+    }
+
+    /// Only show the date
+    pub fn without_time(mut self) -> Self {
+        self.time_length = None;
+        self.date_length = self.date_length.or(Some(DateTimeLength::Medium));
+        self
+    }
+
+    /// Set the locale for this specific translation
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Formats the date and time with the CLDR patterns of the locale,
+    /// falling back to `YYYY-MM-DD hh:mm` if they can't be applied
+    pub(crate) fn format(&self, i18n: &I18n, locale: &str) -> String {
+        let date = self.date_length.map(|date_length| match date_length {
+            DateTimeLength::Short => length::Date::Short,
+            DateTimeLength::Medium => length::Date::Medium,
+            DateTimeLength::Long => length::Date::Long,
+        });
+        let time = self.time_length.map(|time_length| match time_length {
+            DateTimeLength::Short => length::Time::Short,
+            DateTimeLength::Medium | DateTimeLength::Long => length::Time::Medium,
+        });
+        let options = match (date, time) {
+            (Some(date), Some(time)) => length::Bag::from_date_time_style(date, time),
+            (None, Some(time)) => length::Bag::from_time_style(time),
+            (_, None) => length::Bag::from_date_style(date.unwrap_or(length::Date::Medium)),
+        };
+
+        let formatted = self
+            .to_datetime()
+            .map_err(|err| err.to_string())
+            .and_then(|datetime| {
+                let cldr_locale = utils::cldr_locale(i18n, locale)
+                    .parse()
+                    .map_err(|err: icu_locid::ParserError| err.to_string())?;
+                utils::get_datetime_formatter(&cldr_locale, options)
+                    .and_then(|formatter| formatter.format_to_string(&datetime.to_any()))
+                    .map_err(|err| err.to_string())
+            });
+        formatted.unwrap_or_else(|err| {
+            warn!(
+                "Failed to format date: {:?} {:?} with locale: {}, {}",
+                self.date, self.time, locale, err
+            );
+            self.format_iso()
+        })
+    }
+
+    /// Formats the shown parts of the date and time as `YYYY-MM-DD hh:mm`
+    fn format_iso(&self) -> String {
+        let (year, month, day) = self.date;
+        let (hour, minute, _) = self.time;
+        let date = format!("{:04}-{:02}-{:02}", year, month, day);
+        let time = format!("{:02}:{:02}", hour, minute);
+        match (self.date_length, self.time_length) {
+            (Some(_), Some(_)) => format!("{} {}", date, time),
+            (None, Some(_)) => time,
+            (_, None) => date,
+        }
+    }
+
+    fn to_datetime(&self) -> Result<DateTime<icu_calendar::Iso>, InvalidDateTime> {
+        let (year, month, day) = self.date;
+        let (hour, minute, second) = self.time;
+        DateTime::try_new_iso_datetime(year, month, day, hour, minute, second).map_err(|_| {
+            InvalidDateTime(format!(
+                "{}-{}-{} {}:{}:{}",
+                year, month, day, hour, minute, second
+            ))
+        })
+    }
+}
+
+/// Converts days since the unix epoch to a `(year, month, day)` date of the proleptic Gregorian calendar,
+/// `None` if the year doesn't fit in an `i32`
+fn civil_from_days(days: i64) -> Option<(i32, u8, u8)> {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    Some((i32::try_from(year).ok()?, month as u8, day as u8))
+}
+
+impl Component for I18nDateTime {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = Mutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world, HookContext { entity, .. }| {
+            let val = world.get::<Self>(entity).unwrap().clone();
+            let translated = val.translate(world.resource::<I18n>());
+            debug!("Adding i18n date time: {:?}", val.date);
+            if let Some(mut text) = world.get_mut::<Text>(entity) {
+                **text = translated;
+            } else {
+                world
+                    .commands()
+                    .entity(entity)
+                    .insert(Text::new(translated));
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_since_the_unix_epoch() {
+        assert_eq!(civil_from_days(0), Some((1970, 1, 1)));
+        assert_eq!(civil_from_days(-1), Some((1969, 12, 31)));
+        // Leap days, including the 400 year rule
+        assert_eq!(civil_from_days(11_016), Some((2000, 2, 29)));
+        assert_eq!(civil_from_days(19_782), Some((2024, 2, 29)));
+        assert_eq!(civil_from_days(19_783), Some((2024, 3, 1)));
+        assert_eq!(civil_from_days(47_540), Some((2100, 2, 28)));
+        assert_eq!(civil_from_days(47_541), Some((2100, 3, 1)));
+        assert_eq!(civil_from_days(-719_468), Some((0, 3, 1)));
+        // Years past `i32::MAX`
+        assert_eq!(civil_from_days(i64::MAX / 86_400), None);
+    }
+
+    #[test]
+    fn invalid_dates_and_times() {
+        assert_eq!(
+            I18nDateTime::try_new(2025, 2, 29).unwrap_err(),
+            InvalidDateTime("2025-2-29 0:0:0".to_string())
+        );
+        assert!(I18nDateTime::try_new(2024, 2, 29).is_ok());
+        assert!(I18nDateTime::new(2025, 1, 2)
+            .try_with_time(12, 61, 0)
+            .is_err());
+        assert!(I18nDateTime::try_from_unix_timestamp(i64::MAX).is_err());
+        assert_eq!(
+            I18nDateTime::try_from_unix_timestamp(1_735_830_245)
+                .unwrap()
+                .format_iso(),
+            "2025-01-02 15:04"
+        );
+    }
+
+    fn format(date: I18nDateTime, locale: &str) -> String {
+        date.format(&I18n::default(), locale)
+    }
+
+    #[test]
+    fn date_lengths() {
+        let date = |length| I18nDateTime::new(2025, 1, 2).with_date_length(length);
+
+        assert_eq!(format(date(DateTimeLength::Short), "en"), "1/2/25");
+        assert_eq!(format(date(DateTimeLength::Medium), "en"), "Jan 2, 2025");
+        assert_eq!(format(date(DateTimeLength::Long), "en"), "January 2, 2025");
+        assert_eq!(format(date(DateTimeLength::Short), "ja"), "2025/01/02");
+        assert_eq!(format(date(DateTimeLength::Medium), "ja"), "2025/01/02");
+        assert_eq!(format(date(DateTimeLength::Long), "ja"), "2025年1月2日");
+    }
+
+    #[test]
+    fn time_lengths() {
+        let time = |length| {
+            I18nDateTime::new(2025, 1, 2)
+                .with_time(15, 4, 5)
+                .with_time_length(length)
+                .without_date()
+        };
+
+        assert_eq!(format(time(DateTimeLength::Short), "en"), "3:04\u{202f}PM");
+        assert_eq!(
+            format(time(DateTimeLength::Medium), "en"),
+            "3:04:05\u{202f}PM"
+        );
+        assert_eq!(
+            format(time(DateTimeLength::Long), "en"),
+            "3:04:05\u{202f}PM"
+        );
+        assert_eq!(format(time(DateTimeLength::Short), "ja"), "15:04");
+        assert_eq!(format(time(DateTimeLength::Medium), "ja"), "15:04:05");
+    }
+
+    #[test]
+    fn date_and_time() {
+        let date = I18nDateTime::new(2025, 1, 2).with_time(15, 4, 5);
+
+        assert_eq!(format(date.clone(), "en"), "Jan 2, 2025, 3:04\u{202f}PM");
+        assert_eq!(format(date, "ja"), "2025/01/02 15:04");
+    }
+
+    #[test]
+    fn invalid_dates_fall_back_to_iso() {
+        let mut date = I18nDateTime::new(2025, 1, 2).with_time(15, 4, 5);
+        date.date.2 = 32;
+
+        assert_eq!(format(date.clone(), "en"), "2025-01-32 15:04");
+        assert_eq!(format(date.clone().without_time(), "en"), "2025-01-32");
+        assert_eq!(format(date.without_date(), "en"), "15:04");
+    }
+
+    #[test]
+    fn interpolated_dates() {
+        use bevy::platform::collections::HashMap;
+
+        use crate::{assets::TranslationAsset, components::I18nText};
+
+        let asset = TranslationAsset {
+            locales: HashMap::from_iter(["en", "ja"].map(|locale| {
+                (
+                    locale.to_string(),
+                    HashMap::from_iter([("saved".to_string(), "Saved: %{date}".to_string())]),
+                )
+            })),
+        };
+        let mut i18n = I18n::default();
+        i18n.set_translations([&asset]);
+        let text = |locale: &str| {
+            I18nText::new("saved")
+                .with_datetime_arg("date", I18nDateTime::new(2025, 1, 2))
+                .with_locale(locale)
+                .translate(&i18n)
+        };
+
+        assert_eq!(text("en"), "Saved: Jan 2, 2025");
+        assert_eq!(text("ja"), "Saved: 2025/01/02");
+    }
+}
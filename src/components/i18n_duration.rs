@@ -0,0 +1,411 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::{
+        component::{Component, ComponentHook, HookContext, Mutable, StorageType},
+        reflect::ReflectComponent,
+    },
+    log::debug,
+    reflect::Reflect,
+    ui::widget::Text,
+};
+use fixed_decimal::FixedDecimal;
+use icu_decimal::options::GroupingStrategy;
+use icu_list::ListLength;
+
+use crate::resources::I18n;
+
+use super::{utils, I18nComponent, I18nMeasure, ListStyle, MeasureUnit, UnitDisplay};
+
+/// Style of a formatted duration
+#[derive(Default, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DurationStyle {
+    /// Like a digital clock, `1:05:33`, `5:33`, hours are only shown when the duration has some or seconds are hidden
+    #[default]
+    Digital,
+    /// Like a digital clock with 2-digit hours that are always shown, `01:05:33`, `00:05:33`
+    DigitalPadded,
+    /// Abbreviated units, `1 h, 5 min, 33 s`, from the `unit.short.{unit}` translations of the locale
+    Short,
+    /// Abbreviated units joined with the narrow CLDR unit list pattern of the locale, `1 h 5 min 33 s`
+    Narrow,
+    /// Full unit names, `1 hour, 5 minutes, 33 seconds`, from the `unit.long.{unit}` translations of the locale
+    Long,
+}
+
+/// Unit of a duration
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DurationUnit {
+    Second,
+    Minute,
+    Hour,
+    /// Only used by the unit styles, digital durations keep counting hours
+    Day,
+}
+
+impl DurationUnit {
+    fn seconds(&self) -> u64 {
+        match self {
+            Self::Second => 1,
+            Self::Minute => 60,
+            Self::Hour => 3600,
+            Self::Day => 86_400,
+        }
+    }
+
+    fn measure_unit(&self) -> MeasureUnit {
+        match self {
+            Self::Second => MeasureUnit::Second,
+            Self::Minute => MeasureUnit::Minute,
+            Self::Hour => MeasureUnit::Hour,
+            Self::Day => MeasureUnit::Day,
+        }
+    }
+}
+
+/// Makes the duration of an [I18nDuration] move with the time of the app, re-rendering it each frame
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DurationClock {
+    /// Counts up, for race timers and time played
+    Stopwatch,
+    /// Counts down to zero, for cooldowns
+    Countdown,
+}
+
+/// Component for spawning localized duration entities that are managed by `bevy_simple_i18n`,
+/// `1:05:33`, `1 h, 5 min`
+///
+/// It automatically inserts (or replaces) a Bevy `Text` component with the localized duration
+///
+/// The unit styles use the `unit.{display}.{unit}` patterns of [I18nMeasure], joined with the CLDR unit list patterns of the locale
+///
+/// Updates automatically whenever the locale is changed using the [crate::resources::I18n] resource
+///
+/// The duration can also be bound to a source, it is re-rendered whenever the shown value changes,
+/// without re-running the translations of the other components:
+/// - with [I18nDuration::with_clock], it moves with the virtual time of the app
+/// - with [I18nDuration::set_duration], e.g. from a Bevy `Timer` every frame,
+///   in a system that runs before the [I18nSet::Live](crate::prelude::I18nSet::Live) systems
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// # let mut world = World::new();
+/// // Basic usage, `1:05:33`
+/// world.spawn(I18nDuration::new(Duration::from_secs(3933)));
+///
+/// // With units, without the seconds, `1 h, 5 min`
+/// world.spawn(
+///     I18nDuration::new(Duration::from_secs(3933))
+///         .with_style(DurationStyle::Short)
+///         .with_smallest_unit(DurationUnit::Minute),
+/// );
+///
+/// // Counting down from 30 seconds, `0:30`, `0:29`, ..., `0:00`
+/// world.spawn(I18nDuration::new(Duration::from_secs(30)).with_clock(DurationClock::Countdown));
+///
+/// // With forced locale
+/// // overrides the global
+/// // does not update when the locale is changed
+/// world.spawn(I18nDuration::new(Duration::from_secs(3933)).with_locale("ja"));
+/// ```
+#[derive(Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct I18nDuration {
+    /// The duration
+    duration: Duration,
+    /// Style of the formatted duration
+    style: DurationStyle,
+    /// Smallest unit shown, the duration is truncated to it
+    smallest_unit: DurationUnit,
+    /// Moves the duration with the time of the app, `None` to keep it still
+    clock: Option<DurationClock>,
+    /// Shown value in smallest units, to only re-render when it changes
+    rendered: Option<u64>,
+    /// Locale for this specific translation, `None` to use the global locale
+    pub(crate) locale: Option<String>,
+}
+
+impl I18nComponent for I18nDuration {
+    fn locale(&self) -> String {
+        self.locale
+            .clone()
+            .unwrap_or(rust_i18n::locale().to_string())
+    }
+
+    fn translate(&self, i18n: &I18n) -> String {
+        self.format(i18n, &self.locale())
+    }
+}
+
+impl I18nDuration {
+    /// Creates a new `I18nDuration` component with the provided duration
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            style: DurationStyle::default(),
+            smallest_unit: DurationUnit::Second,
+            clock: None,
+            rendered: None,
+            locale: None,
+        }
+    }
+
+    /// Set the style of the formatted duration
+    pub fn with_style(mut self, style: DurationStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the smallest unit shown, the duration is truncated to it, `1:05:33` -> `1:05`
+    pub fn with_smallest_unit(mut self, unit: DurationUnit) -> Self {
+        self.smallest_unit = unit;
+        self
+    }
+
+    /// Move the duration with the virtual time of the app, so it keeps counting without any game code
+    ///
+    /// Only applies to the component, interpolation arguments are rendered once
+    pub fn with_clock(mut self, clock: DurationClock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Set the locale for this specific translation
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Returns the duration
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Set the duration, the text is re-rendered by the [I18nSet::Live](crate::prelude::I18nSet::Live) systems
+    /// if the shown value changed, so systems calling this should run `.before(I18nSet::Live)`
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration = duration;
+    }
+
+    /// Moves the duration with its clock
+    pub(crate) fn tick(&mut self, delta: Duration) {
+        match self.clock {
+            Some(DurationClock::Stopwatch) => self.duration += delta,
+            Some(DurationClock::Countdown) => self.duration = self.duration.saturating_sub(delta),
+            None => {}
+        }
+    }
+
+    /// Returns `true` if the shown value changed since the last render, and marks it as rendered
+    pub(crate) fn needs_render(&mut self) -> bool {
+        let shown = self.duration.as_secs() / self.smallest_unit.seconds();
+        let changed = self.rendered != Some(shown);
+        self.rendered = Some(shown);
+        changed
+    }
+
+    /// Formats the duration with the style of this component in the locale
+    pub(crate) fn format(&self, i18n: &I18n, locale: &str) -> String {
+        let seconds = self.duration.as_secs();
+        let cldr_locale = utils::cldr_locale(i18n, locale);
+        match self.style {
+            DurationStyle::Digital | DurationStyle::DigitalPadded => {
+                let fdf = utils::get_formatter_with_options(
+                    &cldr_locale,
+                    seconds,
+                    GroupingStrategy::Never.into(),
+                );
+                let smallest_unit = self.smallest_unit.min(DurationUnit::Hour);
+                let padded = self.style == DurationStyle::DigitalPadded;
+                let mut fields = vec![];
+                let hours = seconds / 3600;
+                // Without seconds, the hours are always shown, `0:05`
+                if hours > 0 || padded || smallest_unit >= DurationUnit::Minute {
+                    fields.push(FixedDecimal::from(hours));
+                }
+                if smallest_unit <= DurationUnit::Minute {
+                    fields.push(FixedDecimal::from(seconds % 3600 / 60));
+                }
+                if smallest_unit == DurationUnit::Second {
+                    fields.push(FixedDecimal::from(seconds % 60));
+                }
+                fields
+                    .iter_mut()
+                    .enumerate()
+                    .map(|(index, field)| {
+                        // Every field but the first one has 2 digits, `1:05:03`
+                        if index > 0 || padded {
+                            field.pad_start(2);
+                        }
+                        fdf.format_to_string(field)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(":")
+            }
+            DurationStyle::Short | DurationStyle::Narrow | DurationStyle::Long => {
+                let (display, length) = match self.style {
+                    DurationStyle::Long => (UnitDisplay::Long, ListLength::Wide),
+                    DurationStyle::Narrow => (UnitDisplay::Short, ListLength::Narrow),
+                    _ => (UnitDisplay::Short, ListLength::Short),
+                };
+                let mut remaining = seconds;
+                let mut parts = vec![];
+                for unit in [
+                    DurationUnit::Day,
+                    DurationUnit::Hour,
+                    DurationUnit::Minute,
+                    DurationUnit::Second,
+                ] {
+                    if unit < self.smallest_unit {
+                        break;
+                    }
+                    let value = remaining / unit.seconds();
+                    remaining %= unit.seconds();
+                    // Zero durations are still shown in the smallest unit, `0 s`
+                    if value > 0 || (parts.is_empty() && unit == self.smallest_unit) {
+                        parts.push(
+                            I18nMeasure::new(value as f64, unit.measure_unit())
+                                .with_display(display)
+                                .format(i18n, locale),
+                        );
+                    }
+                }
+                utils::get_list_formatter(&cldr_locale, seconds, ListStyle::Unit, length)
+                    .format_to_string(parts.iter())
+            }
+        }
+    }
+}
+
+impl Component for I18nDuration {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = Mutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world, HookContext { entity, .. }| {
+            let val = world.get::<Self>(entity).unwrap().clone();
+            let translated = val.translate(world.resource::<I18n>());
+            debug!("Adding i18n duration: {:?}", val.duration);
+            if let Some(mut text) = world.get_mut::<Text>(entity) {
+                **text = translated;
+            } else {
+                world
+                    .commands()
+                    .entity(entity)
+                    .insert(Text::new(translated));
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::platform::collections::HashMap;
+
+    use super::*;
+    use crate::assets::TranslationAsset;
+
+    const DURATION: Duration = Duration::from_secs(3933);
+
+    fn format(duration: I18nDuration) -> String {
+        let translations = [
+            ("unit.long.hour.one", "%{value} hour"),
+            ("unit.long.hour.other", "%{value} hours"),
+            ("unit.long.minute.one", "%{value} minute"),
+            ("unit.long.minute.other", "%{value} minutes"),
+            ("unit.long.second.one", "%{value} second"),
+            ("unit.long.second.other", "%{value} seconds"),
+        ];
+        let asset = TranslationAsset {
+            locales: HashMap::from_iter([(
+                "en".to_string(),
+                translations
+                    .iter()
+                    .map(|(key, text)| (key.to_string(), text.to_string()))
+                    .collect(),
+            )]),
+        };
+        let mut i18n = I18n::default();
+        i18n.set_translations([&asset]);
+        duration.format(&i18n, "en")
+    }
+
+    #[test]
+    fn digital() {
+        assert_eq!(format(I18nDuration::new(DURATION)), "1:05:33");
+        assert_eq!(format(I18nDuration::new(Duration::from_secs(333))), "5:33");
+        assert_eq!(
+            format(I18nDuration::new(DURATION).with_smallest_unit(DurationUnit::Minute)),
+            "1:05"
+        );
+        assert_eq!(
+            format(I18nDuration::new(Duration::from_secs(90_000))),
+            "25:00:00"
+        );
+    }
+
+    #[test]
+    fn digital_padded() {
+        let padded =
+            |duration| I18nDuration::new(duration).with_style(DurationStyle::DigitalPadded);
+        assert_eq!(format(padded(DURATION)), "01:05:33");
+        assert_eq!(format(padded(Duration::from_secs(333))), "00:05:33");
+        assert_eq!(
+            format(padded(DURATION).with_smallest_unit(DurationUnit::Minute)),
+            "01:05"
+        );
+    }
+
+    #[test]
+    fn short() {
+        let short = |duration| I18nDuration::new(duration).with_style(DurationStyle::Short);
+        assert_eq!(format(short(DURATION)), "1 h, 5 min, 33 s");
+        assert_eq!(format(short(Duration::ZERO)), "0 s");
+    }
+
+    #[test]
+    fn narrow() {
+        let narrow = |duration| I18nDuration::new(duration).with_style(DurationStyle::Narrow);
+        assert_eq!(format(narrow(DURATION)), "1 h 5 min 33 s");
+        assert_eq!(
+            format(narrow(DURATION).with_smallest_unit(DurationUnit::Minute)),
+            "1 h 5 min"
+        );
+    }
+
+    #[test]
+    fn long() {
+        let long = |duration| I18nDuration::new(duration).with_style(DurationStyle::Long);
+        assert_eq!(format(long(DURATION)), "1 hour, 5 minutes, 33 seconds");
+        assert_eq!(
+            format(long(Duration::from_secs(3601)).with_smallest_unit(DurationUnit::Second)),
+            "1 hour, 1 second"
+        );
+    }
+
+    #[test]
+    fn clocks() {
+        let mut stopwatch = I18nDuration::new(DURATION).with_clock(DurationClock::Stopwatch);
+        stopwatch.tick(Duration::from_secs(2));
+        assert_eq!(stopwatch.duration(), Duration::from_secs(3935));
+
+        let mut countdown =
+            I18nDuration::new(Duration::from_secs(1)).with_clock(DurationClock::Countdown);
+        countdown.tick(Duration::from_secs(2));
+        assert_eq!(countdown.duration(), Duration::ZERO);
+    }
+
+    #[test]
+    fn only_renders_when_the_shown_value_changes() {
+        let mut duration = I18nDuration::new(DURATION);
+        assert!(duration.needs_render());
+        duration.set_duration(DURATION + Duration::from_millis(500));
+        assert!(!duration.needs_render());
+        duration.set_duration(DURATION + Duration::from_secs(1));
+        assert!(duration.needs_render());
+    }
+}
@@ -9,7 +9,7 @@ use bevy::{
 };
 
 use crate::{
-    components::{I18nNumber, I18nText, I18nText2d},
+    components::{I18nText, I18nText2d},
     prelude::I18nComponent,
     resources::*,
 };
@@ -20,7 +20,10 @@ use crate::{
 ///
 /// # Example
 ///
-/// ```
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// # let mut world = World::new();
 /// world.spawn((I18nText::new("hello"), I18nFont::new("NotoSans")));
 /// ```
 #[derive(Default, Reflect, Debug, Clone)]
@@ -46,16 +49,19 @@ impl Component for I18nFont {
 
             let locale = if let Some(i18n_text) = world.get::<I18nText>(entity) {
                 i18n_text.locale()
-            } else if let Some(i18n_number) = world.get::<I18nNumber>(entity) {
-                i18n_number.locale()
             } else if let Some(i18n_text_2d) = world.get::<I18nText2d>(entity) {
                 i18n_text_2d.locale()
+            } else if let Some(locale) = numbers_locale(&world, entity) {
+                locale
+            } else if let Some(locale) = datetime_locale(&world, entity) {
+                locale
             } else {
                 rust_i18n::locale().to_string()
             };
 
             let val = world.get::<Self>(entity).unwrap().clone();
-            let font_handler = font_manager.get(&val.0, locale);
+            let locale_chain = world.resource::<I18n>().locale_chain(&locale);
+            let font_handler = font_manager.get(&val.0, &locale_chain);
 
             debug!("Adding dynamic font: {}", val.0);
             if let Some(mut font) = world.get_mut::<TextFont>(entity) {
@@ -69,3 +75,53 @@ impl Component for I18nFont {
         })
     }
 }
+
+#[cfg(feature = "numbers")]
+fn numbers_locale(
+    world: &bevy::ecs::world::DeferredWorld,
+    entity: bevy::ecs::entity::Entity,
+) -> Option<String> {
+    use crate::components::{
+        I18nCurrency, I18nDuration, I18nMeasure, I18nNumber, I18nRelativeTime,
+    };
+
+    if let Some(i18n_number) = world.get::<I18nNumber>(entity) {
+        Some(i18n_number.locale())
+    } else if let Some(i18n_currency) = world.get::<I18nCurrency>(entity) {
+        Some(i18n_currency.locale())
+    } else if let Some(i18n_measure) = world.get::<I18nMeasure>(entity) {
+        Some(i18n_measure.locale())
+    } else if let Some(i18n_relative_time) = world.get::<I18nRelativeTime>(entity) {
+        Some(i18n_relative_time.locale())
+    } else {
+        world
+            .get::<I18nDuration>(entity)
+            .map(|i18n_duration| i18n_duration.locale())
+    }
+}
+
+#[cfg(not(feature = "numbers"))]
+fn numbers_locale(
+    _world: &bevy::ecs::world::DeferredWorld,
+    _entity: bevy::ecs::entity::Entity,
+) -> Option<String> {
+    None
+}
+
+#[cfg(feature = "datetime")]
+fn datetime_locale(
+    world: &bevy::ecs::world::DeferredWorld,
+    entity: bevy::ecs::entity::Entity,
+) -> Option<String> {
+    world
+        .get::<crate::components::I18nDateTime>(entity)
+        .map(|i18n_datetime| i18n_datetime.locale())
+}
+
+#[cfg(not(feature = "datetime"))]
+fn datetime_locale(
+    _world: &bevy::ecs::world::DeferredWorld,
+    _entity: bevy::ecs::entity::Entity,
+) -> Option<String> {
+    None
+}
@@ -0,0 +1,576 @@
+use bevy::{
+    ecs::{
+        component::{Component, ComponentHook, HookContext, Mutable, StorageType},
+        reflect::ReflectComponent,
+    },
+    log::debug,
+    reflect::Reflect,
+    ui::widget::Text,
+};
+use icu_locid_transform::LocaleExpander;
+
+use crate::resources::I18n;
+
+use super::{utils, I18nComponent, I18nNumber, InterpolationType};
+
+/// Key of the unit patterns in the locale files, by display and unit, e.g. `unit.short.kilometer`
+const UNIT_KEY: &str = "unit";
+
+/// Unit of a measure
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeasureUnit {
+    Millimeter,
+    Centimeter,
+    Meter,
+    Kilometer,
+    Inch,
+    Foot,
+    Yard,
+    Mile,
+    Gram,
+    Kilogram,
+    Ounce,
+    Pound,
+    Celsius,
+    Fahrenheit,
+    MeterPerSecond,
+    KilometerPerHour,
+    MilePerHour,
+    Milliliter,
+    Liter,
+    FluidOunce,
+    Gallon,
+    Second,
+    Minute,
+    Hour,
+    Day,
+}
+
+impl MeasureUnit {
+    /// Identifier of the unit in the locale files, the CLDR unit identifier, e.g. `kilometer-per-hour`
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::Millimeter => "millimeter",
+            Self::Centimeter => "centimeter",
+            Self::Meter => "meter",
+            Self::Kilometer => "kilometer",
+            Self::Inch => "inch",
+            Self::Foot => "foot",
+            Self::Yard => "yard",
+            Self::Mile => "mile",
+            Self::Gram => "gram",
+            Self::Kilogram => "kilogram",
+            Self::Ounce => "ounce",
+            Self::Pound => "pound",
+            Self::Celsius => "celsius",
+            Self::Fahrenheit => "fahrenheit",
+            Self::MeterPerSecond => "meter-per-second",
+            Self::KilometerPerHour => "kilometer-per-hour",
+            Self::MilePerHour => "mile-per-hour",
+            Self::Milliliter => "milliliter",
+            Self::Liter => "liter",
+            Self::FluidOunce => "fluid-ounce",
+            Self::Gallon => "gallon",
+            Self::Second => "second",
+            Self::Minute => "minute",
+            Self::Hour => "hour",
+            Self::Day => "day",
+        }
+    }
+
+    /// Symbol used when the locale has no pattern for the unit
+    fn symbol(&self) -> &'static str {
+        match self {
+            Self::Millimeter => "mm",
+            Self::Centimeter => "cm",
+            Self::Meter => "m",
+            Self::Kilometer => "km",
+            Self::Inch => "in",
+            Self::Foot => "ft",
+            Self::Yard => "yd",
+            Self::Mile => "mi",
+            Self::Gram => "g",
+            Self::Kilogram => "kg",
+            Self::Ounce => "oz",
+            Self::Pound => "lb",
+            Self::Celsius => "°C",
+            Self::Fahrenheit => "°F",
+            Self::MeterPerSecond => "m/s",
+            Self::KilometerPerHour => "km/h",
+            Self::MilePerHour => "mph",
+            Self::Milliliter => "mL",
+            Self::Liter => "L",
+            Self::FluidOunce => "fl oz",
+            Self::Gallon => "gal",
+            Self::Second => "s",
+            Self::Minute => "min",
+            Self::Hour => "h",
+            Self::Day => "d",
+        }
+    }
+
+    /// Returns `(factor, offset)` to convert the unit to the base unit of its quantity,
+    /// meters, kilograms, kelvins, meters per second, liters and seconds, `base = value * factor + offset`
+    fn base_conversion(&self) -> (f64, f64) {
+        match self {
+            Self::Millimeter => (0.001, 0.0),
+            Self::Centimeter => (0.01, 0.0),
+            Self::Meter => (1.0, 0.0),
+            Self::Kilometer => (1000.0, 0.0),
+            Self::Inch => (0.0254, 0.0),
+            Self::Foot => (0.3048, 0.0),
+            Self::Yard => (0.9144, 0.0),
+            Self::Mile => (1609.344, 0.0),
+            Self::Gram => (0.001, 0.0),
+            Self::Kilogram => (1.0, 0.0),
+            Self::Ounce => (0.028_349_523_125, 0.0),
+            Self::Pound => (0.453_592_37, 0.0),
+            Self::Celsius => (1.0, 273.15),
+            Self::Fahrenheit => (5.0 / 9.0, 273.15 - 32.0 * 5.0 / 9.0),
+            Self::MeterPerSecond => (1.0, 0.0),
+            Self::KilometerPerHour => (1.0 / 3.6, 0.0),
+            Self::MilePerHour => (0.447_04, 0.0),
+            Self::Milliliter => (0.001, 0.0),
+            Self::Liter => (1.0, 0.0),
+            Self::FluidOunce => (0.029_573_529_562_5, 0.0),
+            Self::Gallon => (3.785_411_784, 0.0),
+            Self::Second => (1.0, 0.0),
+            Self::Minute => (60.0, 0.0),
+            Self::Hour => (3600.0, 0.0),
+            Self::Day => (86_400.0, 0.0),
+        }
+    }
+
+    /// Returns the matching unit of the measurement system, or the unit itself if it is already part of it
+    fn in_system(&self, system: MeasurementSystem) -> Self {
+        match system {
+            MeasurementSystem::Metric => match self {
+                Self::Inch => Self::Centimeter,
+                Self::Foot | Self::Yard => Self::Meter,
+                Self::Mile => Self::Kilometer,
+                Self::Ounce => Self::Gram,
+                Self::Pound => Self::Kilogram,
+                Self::Fahrenheit => Self::Celsius,
+                Self::MilePerHour => Self::KilometerPerHour,
+                Self::FluidOunce => Self::Milliliter,
+                Self::Gallon => Self::Liter,
+                unit => *unit,
+            },
+            // The UK uses miles for distances and speeds, but metric masses, volumes and temperatures
+            MeasurementSystem::UnitedKingdom => match self {
+                Self::Kilometer | Self::Mile => Self::Mile,
+                Self::MeterPerSecond | Self::KilometerPerHour | Self::MilePerHour => {
+                    Self::MilePerHour
+                }
+                unit => unit.in_system(MeasurementSystem::Metric),
+            },
+            MeasurementSystem::UnitedStates => match self {
+                Self::Millimeter | Self::Centimeter => Self::Inch,
+                Self::Meter => Self::Foot,
+                Self::Kilometer => Self::Mile,
+                Self::Gram => Self::Ounce,
+                Self::Kilogram => Self::Pound,
+                Self::Celsius => Self::Fahrenheit,
+                Self::MeterPerSecond | Self::KilometerPerHour => Self::MilePerHour,
+                Self::Milliliter => Self::FluidOunce,
+                Self::Liter => Self::Gallon,
+                unit => *unit,
+            },
+        }
+    }
+
+    /// Converts the value from this unit to another unit of the same quantity
+    fn convert(&self, value: f64, to: Self) -> f64 {
+        let (factor, offset) = self.base_conversion();
+        let (to_factor, to_offset) = to.base_conversion();
+        (value * factor + offset - to_offset) / to_factor
+    }
+}
+
+/// Measurement system used by a locale
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeasurementSystem {
+    /// Metric units, `km`, `kg`, `°C`
+    Metric,
+    /// Metric units, except miles for distances and speeds
+    UnitedKingdom,
+    /// US customary units, `mi`, `lb`, `°F`
+    UnitedStates,
+}
+
+impl MeasurementSystem {
+    /// Returns the measurement system of the region of the locale,
+    /// the likely region is used when the locale has none, `en` -> `en-US`
+    pub fn for_locale(locale: &str) -> Self {
+        let Ok(mut locale) = locale.parse::<icu_locid::Locale>() else {
+            return Self::Metric;
+        };
+        LocaleExpander::new().maximize(&mut locale.id);
+        match locale.id.region.as_ref().map(|region| region.as_str()) {
+            Some("US" | "LR" | "MM") => Self::UnitedStates,
+            Some("GB") => Self::UnitedKingdom,
+            _ => Self::Metric,
+        }
+    }
+}
+
+/// How the unit is displayed next to the value
+#[derive(Default, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitDisplay {
+    /// Abbreviated unit, `12 km`, from the `unit.short.{unit}` translations of the locale
+    #[default]
+    Short,
+    /// Full unit name, `12 kilometers`, from the `unit.long.{unit}` translations of the locale
+    Long,
+}
+
+impl UnitDisplay {
+    fn id(&self) -> &'static str {
+        match self {
+            Self::Short => "short",
+            Self::Long => "long",
+        }
+    }
+}
+
+/// Component for spawning localized measure entities that are managed by `bevy_simple_i18n`
+///
+/// It automatically inserts (or replaces) a Bevy `Text` component with the localized value and unit
+///
+/// Units of length, mass, temperature, speed, volume and time are supported
+///
+/// The unit patterns are read from the `unit.{display}.{unit}` translations of the locale,
+/// using the CLDR plural rules of the locale and the `%{value}` argument,
+/// when the locale has no pattern for the unit, the value is followed by the unit symbol, `12 km`
+///
+/// Updates automatically whenever the locale is changed using the [crate::resources::I18n] resource
+///
+/// # Example
+///
+/// ```yml
+/// unit.long.kilometer:
+///   en:
+///     one: '%{value} kilometer'
+///     other: '%{value} kilometers'
+/// ```
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// # let mut world = World::new();
+/// // Basic usage, `12 km`
+/// world.spawn(I18nMeasure::new(12, MeasureUnit::Kilometer));
+///
+/// // Converted to the measurement system of the locale, `7.5 mi` in `en-US`, `12 km` in `fr`
+/// world.spawn(I18nMeasure::new(12, MeasureUnit::Kilometer).with_preferred_units());
+///
+/// // With the full unit name, `20 degrees Celsius`
+/// world.spawn(I18nMeasure::new(20, MeasureUnit::Celsius).with_display(UnitDisplay::Long));
+///
+/// // With forced locale
+/// // overrides the global
+/// // does not update when the locale is changed
+/// world.spawn(I18nMeasure::new(12, MeasureUnit::Kilometer).with_locale("ja"));
+/// ```
+#[derive(Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct I18nMeasure {
+    /// Value in the unit
+    value: f64,
+    /// Unit of the value
+    unit: MeasureUnit,
+    /// How the unit is displayed
+    display: UnitDisplay,
+    /// Converts the value to the measurement system of the locale
+    preferred_units: bool,
+    /// Maximum number of fraction digits of the value
+    max_fraction_digits: u8,
+    /// Locale for this specific translation, `None` to use the global locale
+    pub(crate) locale: Option<String>,
+}
+
+impl I18nComponent for I18nMeasure {
+    fn locale(&self) -> String {
+        self.locale
+            .clone()
+            .unwrap_or(rust_i18n::locale().to_string())
+    }
+
+    fn translate(&self, i18n: &I18n) -> String {
+        self.format(i18n, &self.locale())
+    }
+}
+
+impl I18nMeasure {
+    /// Creates a new `I18nMeasure` component with the provided value and unit
+    pub fn new(value: impl Into<f64>, unit: MeasureUnit) -> Self {
+        Self {
+            value: value.into(),
+            unit,
+            display: UnitDisplay::default(),
+            preferred_units: false,
+            max_fraction_digits: 1,
+            locale: None,
+        }
+    }
+
+    /// Set how the unit is displayed next to the value
+    pub fn with_display(mut self, display: UnitDisplay) -> Self {
+        self.display = display;
+        self
+    }
+
+    /// Convert the value to the [MeasurementSystem] of the locale, `12 km` -> `7.5 mi` in `en-US`
+    pub fn with_preferred_units(mut self) -> Self {
+        self.preferred_units = true;
+        self
+    }
+
+    /// Set the maximum number of fraction digits of the value, 1 by default
+    pub fn with_max_fraction_digits(mut self, digits: u8) -> Self {
+        self.max_fraction_digits = digits;
+        self
+    }
+
+    /// Set the locale for this specific translation
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Formats the value and unit in the locale, converted to its measurement system if enabled
+    pub(crate) fn format(&self, i18n: &I18n, locale: &str) -> String {
+        let (value, unit) = if self.preferred_units {
+            let unit = self.unit.in_system(MeasurementSystem::for_locale(locale));
+            (self.unit.convert(self.value, unit), unit)
+        } else {
+            (self.value, self.unit)
+        };
+        let number = I18nNumber::new(value).with_max_fraction_digits(self.max_fraction_digits);
+
+        let key = format!("{}.{}.{}", UNIT_KEY, self.display.id(), unit.id());
+        let args = [(
+            "value".to_string(),
+            InterpolationType::Number(number.clone()),
+        )];
+        let translated = utils::translate_by_key(i18n, locale, &key, None, &args);
+        if translated != key {
+            return translated;
+        }
+        format!("{} {}", number.format(i18n, locale), unit.symbol())
+    }
+}
+
+impl Component for I18nMeasure {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = Mutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world, HookContext { entity, .. }| {
+            let val = world.get::<Self>(entity).unwrap().clone();
+            let translated = val.translate(world.resource::<I18n>());
+            debug!("Adding i18n measure: {} {}", val.value, val.unit.id());
+            if let Some(mut text) = world.get_mut::<Text>(entity) {
+                **text = translated;
+            } else {
+                world
+                    .commands()
+                    .entity(entity)
+                    .insert(Text::new(translated));
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::platform::collections::HashMap;
+
+    use super::*;
+    use crate::assets::TranslationAsset;
+
+    fn assert_close(value: f64, expected: f64) {
+        assert!(
+            (value - expected).abs() < 1e-9,
+            "{} is not {}",
+            value,
+            expected
+        );
+    }
+
+    fn i18n(translations: &[(&str, &str)]) -> I18n {
+        let asset = TranslationAsset {
+            locales: HashMap::from_iter([(
+                "en".to_string(),
+                translations
+                    .iter()
+                    .map(|(key, text)| (key.to_string(), text.to_string()))
+                    .collect(),
+            )]),
+        };
+        let mut i18n = I18n::default();
+        i18n.set_translations([&asset]);
+        i18n
+    }
+
+    #[test]
+    fn conversions() {
+        assert_close(
+            MeasureUnit::Kilometer.convert(12.0, MeasureUnit::Mile),
+            7.456_454_306_848_007,
+        );
+        assert_close(
+            MeasureUnit::Mile.convert(1.0, MeasureUnit::Kilometer),
+            1.609_344,
+        );
+        assert_close(
+            MeasureUnit::Pound.convert(1.0, MeasureUnit::Gram),
+            453.592_37,
+        );
+        assert_close(
+            MeasureUnit::KilometerPerHour.convert(36.0, MeasureUnit::MeterPerSecond),
+            10.0,
+        );
+        assert_close(MeasureUnit::Hour.convert(1.5, MeasureUnit::Minute), 90.0);
+        assert_close(
+            MeasureUnit::Gallon.convert(1.0, MeasureUnit::Liter),
+            3.785_411_784,
+        );
+    }
+
+    #[test]
+    fn temperature_conversions_apply_the_offset() {
+        assert_eq!(MeasureUnit::Celsius.base_conversion(), (1.0, 273.15));
+        assert_close(
+            MeasureUnit::Celsius.convert(100.0, MeasureUnit::Fahrenheit),
+            212.0,
+        );
+        assert_close(
+            MeasureUnit::Celsius.convert(0.0, MeasureUnit::Fahrenheit),
+            32.0,
+        );
+        assert_close(
+            MeasureUnit::Fahrenheit.convert(-40.0, MeasureUnit::Celsius),
+            -40.0,
+        );
+        assert_close(
+            MeasureUnit::Fahrenheit.convert(98.6, MeasureUnit::Celsius),
+            37.0,
+        );
+    }
+
+    #[test]
+    fn units_of_the_measurement_systems() {
+        let in_system = |unit: MeasureUnit, system| unit.in_system(system);
+
+        assert_eq!(
+            in_system(MeasureUnit::Mile, MeasurementSystem::Metric),
+            MeasureUnit::Kilometer
+        );
+        assert_eq!(
+            in_system(MeasureUnit::Fahrenheit, MeasurementSystem::Metric),
+            MeasureUnit::Celsius
+        );
+        assert_eq!(
+            in_system(MeasureUnit::Second, MeasurementSystem::Metric),
+            MeasureUnit::Second
+        );
+        assert_eq!(
+            in_system(MeasureUnit::Kilometer, MeasurementSystem::UnitedKingdom),
+            MeasureUnit::Mile
+        );
+        assert_eq!(
+            in_system(
+                MeasureUnit::KilometerPerHour,
+                MeasurementSystem::UnitedKingdom
+            ),
+            MeasureUnit::MilePerHour
+        );
+        assert_eq!(
+            in_system(MeasureUnit::Pound, MeasurementSystem::UnitedKingdom),
+            MeasureUnit::Kilogram
+        );
+        assert_eq!(
+            in_system(MeasureUnit::Celsius, MeasurementSystem::UnitedStates),
+            MeasureUnit::Fahrenheit
+        );
+        assert_eq!(
+            in_system(MeasureUnit::Centimeter, MeasurementSystem::UnitedStates),
+            MeasureUnit::Inch
+        );
+        assert_eq!(
+            in_system(MeasureUnit::Mile, MeasurementSystem::UnitedStates),
+            MeasureUnit::Mile
+        );
+    }
+
+    #[test]
+    fn measurement_system_of_the_locale() {
+        assert_eq!(
+            MeasurementSystem::for_locale("en"),
+            MeasurementSystem::UnitedStates
+        );
+        assert_eq!(
+            MeasurementSystem::for_locale("en-US"),
+            MeasurementSystem::UnitedStates
+        );
+        assert_eq!(
+            MeasurementSystem::for_locale("en-GB"),
+            MeasurementSystem::UnitedKingdom
+        );
+        assert_eq!(
+            MeasurementSystem::for_locale("fr"),
+            MeasurementSystem::Metric
+        );
+        assert_eq!(
+            MeasurementSystem::for_locale("en-CA"),
+            MeasurementSystem::Metric
+        );
+        assert_eq!(
+            MeasurementSystem::for_locale("not a locale"),
+            MeasurementSystem::Metric
+        );
+    }
+
+    #[test]
+    fn unit_patterns_fall_back_to_the_symbol() {
+        let i18n = i18n(&[
+            ("unit.long.kilometer.one", "%{value} kilometer"),
+            ("unit.long.kilometer.other", "%{value} kilometers"),
+        ]);
+        let format = |measure: I18nMeasure| measure.format(&i18n, "en");
+
+        assert_eq!(
+            format(I18nMeasure::new(1, MeasureUnit::Kilometer).with_display(UnitDisplay::Long)),
+            "1 kilometer"
+        );
+        assert_eq!(
+            format(I18nMeasure::new(12.5, MeasureUnit::Kilometer).with_display(UnitDisplay::Long)),
+            "12.5 kilometers"
+        );
+        assert_eq!(
+            format(I18nMeasure::new(12, MeasureUnit::Kilometer)),
+            "12 km"
+        );
+        assert_eq!(
+            format(I18nMeasure::new(1234.56, MeasureUnit::Celsius).with_display(UnitDisplay::Long)),
+            "1,234.6 °C"
+        );
+    }
+
+    #[test]
+    fn preferred_units() {
+        let i18n = I18n::default();
+        let measure = I18nMeasure::new(12, MeasureUnit::Kilometer).with_preferred_units();
+
+        assert_eq!(measure.format(&i18n, "en"), "7.5 mi");
+        assert_eq!(measure.format(&i18n, "en-GB"), "7.5 mi");
+        assert_eq!(measure.format(&i18n, "fr"), "12 km");
+        assert_eq!(
+            I18nMeasure::new(20, MeasureUnit::Celsius)
+                .with_preferred_units()
+                .format(&i18n, "en"),
+            "68 °F"
+        );
+    }
+}
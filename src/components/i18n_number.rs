@@ -8,9 +8,86 @@ use bevy::{
     ui::widget::Text,
 };
 use fixed_decimal::FixedDecimal;
+use icu_decimal::options::{FixedDecimalFormatterOptions, GroupingStrategy};
+
+use crate::resources::I18n;
 
 use super::{utils, I18nComponent};
 
+/// Notation of a formatted number
+#[derive(Default, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberNotation {
+    /// Full number with the grouping separators of the locale, `1,234,567`
+    #[default]
+    Standard,
+    /// Short compact notation with the CLDR compact patterns of the locale, `1.2M`, `123万`
+    CompactShort,
+    /// Long compact notation with the CLDR compact patterns of the locale, `1.2 million`
+    CompactLong,
+    /// Scientific notation, `1.235E6`
+    ///
+    /// The exponent separator is always `E`, the CLDR exponential symbol of the locale isn't part of the ICU4X data
+    Scientific,
+    /// Engineering notation, the exponent is a multiple of 3, `1.235E6`, `12.346E3`
+    Engineering,
+}
+
+/// Rounding mode used when the number has more fraction digits than the maximum
+#[derive(Default, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberRounding {
+    /// Towards positive infinity, `1.21` -> `1.3`, `-1.29` -> `-1.2`
+    Ceil,
+    /// Towards negative infinity, `1.29` -> `1.2`, `-1.21` -> `-1.3`
+    Floor,
+    /// Away from zero, `1.21` -> `1.3`
+    Expand,
+    /// Towards zero, `1.29` -> `1.2`
+    Trunc,
+    /// To the nearest, ties towards positive infinity
+    HalfCeil,
+    /// To the nearest, ties towards negative infinity
+    HalfFloor,
+    /// To the nearest, ties away from zero, `1.25` -> `1.3`
+    #[default]
+    HalfExpand,
+    /// To the nearest, ties towards zero, `1.25` -> `1.2`
+    HalfTrunc,
+    /// To the nearest, ties to the even digit, `1.25` -> `1.2`, `1.35` -> `1.4`
+    HalfEven,
+}
+
+/// When the grouping separators of the locale are shown, `1,234`
+#[derive(Default, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberGrouping {
+    /// Follows the CLDR minimum grouping digits of the locale, `1,234` in `en` but `1234` in `es`
+    #[default]
+    Auto,
+    /// Never group digits, `1234567`
+    Never,
+    /// Always group digits, `1,234`
+    ///
+    /// ICU4X 1.5 still applies the CLDR minimum grouping digits of the locale, so this is the same as `Auto` for now
+    Always,
+    /// Only group numbers with at least 5 integer digits, `1234` and `12,345`
+    Min2,
+}
+
+/// When the sign of the number is shown
+#[derive(Default, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberSign {
+    /// Only for negative numbers, including negative zero, `-1`, `0`, `1`
+    #[default]
+    Auto,
+    /// Never show the sign, `1`, `0`, `1`
+    Never,
+    /// Always show the sign, `-1`, `+0`, `+1`
+    Always,
+    /// For every number except zero, `-1`, `0`, `+1`
+    ExceptZero,
+    /// Only for negative numbers, excluding negative zero, `-1`, `0`, `1`
+    Negative,
+}
+
 /// Component for spawning translatable number entities that are managed by `bevy_simple_i18n`
 ///
 /// It automatically inserts (or replaces) a Bevy `Text` component with the localized number
@@ -19,10 +96,27 @@ use super::{utils, I18nComponent};
 ///
 /// # Example
 ///
-/// ```
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// # let mut world = World::new();
 /// // Basic usage
 /// world.spawn(I18nNumber::new(200.40));
 ///
+/// // With compact notation, `1.2M`
+/// world.spawn(I18nNumber::new(1_234_567).with_notation(NumberNotation::CompactShort));
+///
+/// // With two fraction digits and an explicit sign, `+1,234.50`
+/// world.spawn(
+///     I18nNumber::new(1234.5)
+///         .with_min_fraction_digits(2)
+///         .with_max_fraction_digits(2)
+///         .with_sign(NumberSign::ExceptZero),
+/// );
+///
+/// // As an ordinal, `3rd`
+/// world.spawn(I18nNumber::new(3).with_ordinal());
+///
 /// // With forced locale
 /// // overrides the global
 /// // does not update when the locale is changed
@@ -33,6 +127,22 @@ use super::{utils, I18nComponent};
 pub struct I18nNumber {
     #[reflect(ignore)]
     pub(crate) fixed_decimal: FixedDecimal,
+    /// Notation of the formatted number
+    notation: NumberNotation,
+    /// Minimum number of fraction digits, padded with zeros
+    min_fraction_digits: Option<u8>,
+    /// Maximum number of fraction digits, rounded with the rounding mode
+    max_fraction_digits: Option<u8>,
+    /// Minimum number of integer digits, padded with zeros
+    min_integer_digits: Option<u8>,
+    /// Rounding mode used for the maximum fraction digits
+    rounding: NumberRounding,
+    /// When the grouping separators are shown
+    grouping: NumberGrouping,
+    /// When the sign is shown
+    sign: NumberSign,
+    /// Formats the number as an ordinal, `1st`, `2nd`, `3rd`
+    pub(crate) ordinal: bool,
     /// Locale for this specific translation, `None` to use the global locale
     pub(crate) locale: Option<String>,
 }
@@ -44,26 +154,221 @@ impl I18nComponent for I18nNumber {
             .unwrap_or(rust_i18n::locale().to_string())
     }
 
-    fn translate(&self) -> String {
-        utils::get_formatter(&self.locale(), &self.fixed_decimal)
-            .format_to_string(&self.fixed_decimal)
+    fn translate(&self, i18n: &I18n) -> String {
+        self.format(i18n, &self.locale())
     }
 }
 
 impl I18nNumber {
     /// Creates a new `I18nNumber` component with the provided number value
     pub fn new(number: impl Into<f64>) -> Self {
+        Self::from_fixed_decimal(utils::f64_to_fd(number.into()))
+    }
+
+    pub(crate) fn from_fixed_decimal(fixed_decimal: FixedDecimal) -> Self {
         Self {
-            fixed_decimal: utils::f64_to_fd(number.into()),
+            fixed_decimal,
+            notation: NumberNotation::default(),
+            min_fraction_digits: None,
+            max_fraction_digits: None,
+            min_integer_digits: None,
+            rounding: NumberRounding::default(),
+            grouping: NumberGrouping::default(),
+            sign: NumberSign::default(),
+            ordinal: false,
             locale: None,
         }
     }
 
+    /// Set the notation of the formatted number
+    pub fn with_notation(mut self, notation: NumberNotation) -> Self {
+        self.notation = notation;
+        self
+    }
+
+    /// Set the minimum number of fraction digits, the number is padded with zeros, `1.5` -> `1.50`
+    ///
+    /// With the scientific notations, this applies to the significand
+    pub fn with_min_fraction_digits(mut self, digits: u8) -> Self {
+        self.min_fraction_digits = Some(digits);
+        self
+    }
+
+    /// Set the maximum number of fraction digits, the number is rounded with the rounding mode, `1.2345` -> `1.23`
+    ///
+    /// With the scientific notations, this applies to the significand, which keeps 3 fraction digits by default
+    pub fn with_max_fraction_digits(mut self, digits: u8) -> Self {
+        self.max_fraction_digits = Some(digits);
+        self
+    }
+
+    /// Set the minimum number of integer digits, the number is padded with zeros, `7` -> `007`
+    pub fn with_min_integer_digits(mut self, digits: u8) -> Self {
+        self.min_integer_digits = Some(digits);
+        self
+    }
+
+    /// Set the rounding mode used for the maximum fraction digits, [NumberRounding::HalfExpand] by default
+    pub fn with_rounding(mut self, rounding: NumberRounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Set when the grouping separators of the locale are shown
+    pub fn with_grouping(mut self, grouping: NumberGrouping) -> Self {
+        self.grouping = grouping;
+        self
+    }
+
+    /// Set when the sign of the number is shown
+    pub fn with_sign(mut self, sign: NumberSign) -> Self {
+        self.sign = sign;
+        self
+    }
+
+    /// Format the number as an ordinal, `1st`, `2nd`, `3rd`
+    ///
+    /// The suffix patterns are the `ordinal.{category}` translations of the locale,
+    /// selected with the CLDR ordinal plural rules, e.g. `ordinal.few: "%{number}rd"`
+    ///
+    /// Ordinals are always formatted with the standard notation
+    pub fn with_ordinal(mut self) -> Self {
+        self.ordinal = true;
+        self
+    }
+
     /// Set the locale for this specific translation
     pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
         self.locale = Some(locale.into());
         self
     }
+
+    /// Formats the number with the notation and options of this component in the locale
+    ///
+    /// The compact notations use the rounding of the CLDR compact patterns,
+    /// so the fraction and integer digits options don't apply to them
+    pub(crate) fn format(&self, i18n: &I18n, locale: &str) -> String {
+        let options = self.formatter_options();
+        let cldr_locale = utils::cldr_locale(i18n, locale);
+        if self.ordinal {
+            let number = self.apply_options(self.fixed_decimal.clone(), None);
+            let fdf = utils::get_formatter_with_options(&cldr_locale, &number, options);
+            return utils::format_ordinal(i18n, locale, &fdf, &number);
+        }
+        match self.notation {
+            NumberNotation::Standard => {
+                let number = self.apply_options(self.fixed_decimal.clone(), None);
+                utils::get_formatter_with_options(&cldr_locale, &number, options)
+                    .format_to_string(&number)
+            }
+            NumberNotation::CompactShort | NumberNotation::CompactLong => {
+                let mut number = self.fixed_decimal.clone();
+                number.apply_sign_display(self.sign_display());
+                let long = self.notation == NumberNotation::CompactLong;
+                // CLDR compact numbers only group from 5 integer digits by default, `1234万`
+                let options = match self.grouping {
+                    NumberGrouping::Auto => GroupingStrategy::Min2.into(),
+                    _ => options,
+                };
+                utils::get_compact_formatter(&cldr_locale, &number, long, options)
+                    .format_fixed_decimal(number)
+                    .to_string()
+            }
+            NumberNotation::Scientific => self.format_scientific(&cldr_locale, 1),
+            NumberNotation::Engineering => self.format_scientific(&cldr_locale, 3),
+        }
+    }
+
+    /// Returns the number used to select plural forms, with the fraction digits it is formatted with,
+    /// `1.00` is plural in English
+    pub(crate) fn plural_operand(&self) -> FixedDecimal {
+        match self.notation {
+            NumberNotation::Standard => self.apply_options(self.fixed_decimal.clone(), None),
+            _ => self.fixed_decimal.clone(),
+        }
+    }
+
+    /// Rounds and pads the number with the fraction and integer digits options, then applies the sign display
+    ///
+    /// `default_max_fraction_digits` is used when no maximum was set
+    fn apply_options(
+        &self,
+        mut number: FixedDecimal,
+        default_max_fraction_digits: Option<u8>,
+    ) -> FixedDecimal {
+        let min_fraction_digits = self.min_fraction_digits.unwrap_or(0);
+        if let Some(max_fraction_digits) = self.max_fraction_digits.or(default_max_fraction_digits)
+        {
+            let position = -i16::from(max_fraction_digits.max(min_fraction_digits));
+            match self.rounding {
+                NumberRounding::Ceil => number.ceil(position),
+                NumberRounding::Floor => number.floor(position),
+                NumberRounding::Expand => number.expand(position),
+                NumberRounding::Trunc => number.trunc(position),
+                NumberRounding::HalfCeil => number.half_ceil(position),
+                NumberRounding::HalfFloor => number.half_floor(position),
+                NumberRounding::HalfExpand => number.half_expand(position),
+                NumberRounding::HalfTrunc => number.half_trunc(position),
+                NumberRounding::HalfEven => number.half_even(position),
+            }
+            number.trim_end();
+        }
+        number.pad_end(-i16::from(min_fraction_digits));
+        if let Some(min_integer_digits) = self.min_integer_digits {
+            number.pad_start(i16::from(min_integer_digits));
+        }
+        number.apply_sign_display(self.sign_display());
+        number
+    }
+
+    fn formatter_options(&self) -> FixedDecimalFormatterOptions {
+        match self.grouping {
+            NumberGrouping::Auto => GroupingStrategy::Auto,
+            NumberGrouping::Never => GroupingStrategy::Never,
+            NumberGrouping::Always => GroupingStrategy::Always,
+            NumberGrouping::Min2 => GroupingStrategy::Min2,
+        }
+        .into()
+    }
+
+    fn sign_display(&self) -> fixed_decimal::SignDisplay {
+        match self.sign {
+            NumberSign::Auto => fixed_decimal::SignDisplay::Auto,
+            NumberSign::Never => fixed_decimal::SignDisplay::Never,
+            NumberSign::Always => fixed_decimal::SignDisplay::Always,
+            NumberSign::ExceptZero => fixed_decimal::SignDisplay::ExceptZero,
+            NumberSign::Negative => fixed_decimal::SignDisplay::Negative,
+        }
+    }
+
+    /// Formats the number as `{significand}E{exponent}` with the digits and separators of the locale,
+    /// the exponent is a multiple of `step`, 1 for scientific and 3 for engineering notation
+    ///
+    /// The significand keeps at most 3 fraction digits by default and is rounded with the rounding mode of this component.
+    /// ICU4X 1.5 has no scientific formatter nor the CLDR exponential symbols,
+    /// so `E` is used in every locale, where CLDR uses e.g. `×10^` in Swedish
+    fn format_scientific(&self, locale: &str, step: i16) -> String {
+        let number = &self.fixed_decimal;
+        let fdf = utils::get_formatter_with_options(locale, number, self.formatter_options());
+        let magnitude = if number.is_zero() {
+            0
+        } else {
+            number.nonzero_magnitude_start()
+        };
+        let mut exponent = magnitude.div_euclid(step) * step;
+        let significand = number.clone().multiplied_pow10(-exponent).trimmed_start();
+        let mut significand = self.apply_options(significand, Some(3));
+        // Rounding can carry into a new digit, `9.9996E2` -> `1E3`
+        if !significand.is_zero() && significand.nonzero_magnitude_start() >= step {
+            exponent += step;
+            significand = self.apply_options(number.clone().multiplied_pow10(-exponent), Some(3));
+        }
+        format!(
+            "{}E{}",
+            fdf.format_to_string(&significand),
+            fdf.format_to_string(&FixedDecimal::from(exponent))
+        )
+    }
 }
 
 impl Component for I18nNumber {
@@ -73,15 +378,192 @@ impl Component for I18nNumber {
     fn on_add() -> Option<ComponentHook> {
         Some(|mut world, HookContext { entity, .. }| {
             let val = world.get::<Self>(entity).unwrap().clone();
+            let translated = val.translate(world.resource::<I18n>());
             debug!("Adding i18n number: {}", val.fixed_decimal);
             if let Some(mut text) = world.get_mut::<Text>(entity) {
-                **text = val.translate();
+                **text = translated;
             } else {
                 world
                     .commands()
                     .entity(entity)
-                    .insert(Text::new(val.translate()));
+                    .insert(Text::new(translated));
             }
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(number: I18nNumber, locale: &str) -> String {
+        number.format(&I18n::default(), locale)
+    }
+
+    #[test]
+    fn fraction_digits() {
+        let digits = |value: f64, min: u8, max: u8| {
+            I18nNumber::new(value)
+                .with_min_fraction_digits(min)
+                .with_max_fraction_digits(max)
+        };
+        assert_eq!(format(digits(1234.5, 2, 2), "en"), "1,234.50");
+        assert_eq!(format(digits(1.23456, 0, 3), "en"), "1.235");
+        assert_eq!(format(digits(1.2, 0, 3), "en"), "1.2");
+        assert_eq!(format(digits(2.5, 0, 0), "en"), "3");
+        // The minimum wins over a lower maximum
+        assert_eq!(format(digits(1.25, 3, 1), "en"), "1.250");
+        assert_eq!(format(digits(1234.5, 2, 2), "de"), "1.234,50");
+    }
+
+    #[test]
+    fn rounding_modes() {
+        let round = |value: f64, rounding: NumberRounding| {
+            format(
+                I18nNumber::new(value)
+                    .with_max_fraction_digits(1)
+                    .with_rounding(rounding),
+                "en",
+            )
+        };
+        assert_eq!(round(1.21, NumberRounding::Ceil), "1.3");
+        assert_eq!(round(-1.29, NumberRounding::Ceil), "-1.2");
+        assert_eq!(round(1.29, NumberRounding::Floor), "1.2");
+        assert_eq!(round(-1.21, NumberRounding::Floor), "-1.3");
+        assert_eq!(round(1.21, NumberRounding::Expand), "1.3");
+        assert_eq!(round(-1.21, NumberRounding::Expand), "-1.3");
+        assert_eq!(round(1.29, NumberRounding::Trunc), "1.2");
+        assert_eq!(round(-1.29, NumberRounding::Trunc), "-1.2");
+        assert_eq!(round(-1.25, NumberRounding::HalfCeil), "-1.2");
+        assert_eq!(round(1.25, NumberRounding::HalfFloor), "1.2");
+        assert_eq!(round(1.25, NumberRounding::HalfExpand), "1.3");
+        assert_eq!(round(-1.25, NumberRounding::HalfExpand), "-1.3");
+        assert_eq!(round(1.25, NumberRounding::HalfTrunc), "1.2");
+        assert_eq!(round(1.25, NumberRounding::HalfEven), "1.2");
+        assert_eq!(round(1.35, NumberRounding::HalfEven), "1.4");
+    }
+
+    #[test]
+    fn grouping_strategies() {
+        let group = |value: f64, grouping: NumberGrouping, locale: &str| {
+            format(I18nNumber::new(value).with_grouping(grouping), locale)
+        };
+        assert_eq!(group(1234.0, NumberGrouping::Auto, "en"), "1,234");
+        assert_eq!(group(1234.0, NumberGrouping::Never, "en"), "1234");
+        assert_eq!(group(1234.0, NumberGrouping::Always, "en"), "1,234");
+        assert_eq!(group(1234.0, NumberGrouping::Min2, "en"), "1234");
+        assert_eq!(group(12345.0, NumberGrouping::Min2, "en"), "12,345");
+        // Separators and minimum grouping digits of the locale
+        assert_eq!(group(1_234_567.0, NumberGrouping::Auto, "de"), "1.234.567");
+        assert_eq!(
+            group(1_234_567.0, NumberGrouping::Auto, "fr"),
+            "1\u{202f}234\u{202f}567"
+        );
+        assert_eq!(group(1234.0, NumberGrouping::Auto, "es"), "1234");
+        assert_eq!(group(1234.0, NumberGrouping::Always, "es"), "1234");
+        assert_eq!(group(12345.0, NumberGrouping::Auto, "es"), "12.345");
+    }
+
+    #[test]
+    fn sign_display() {
+        let sign =
+            |value: f64, sign: NumberSign| format(I18nNumber::new(value).with_sign(sign), "en");
+        assert_eq!(sign(-1.0, NumberSign::Auto), "-1");
+        assert_eq!(sign(1.0, NumberSign::Auto), "1");
+        assert_eq!(sign(-1.0, NumberSign::Never), "1");
+        assert_eq!(sign(1.0, NumberSign::Always), "+1");
+        assert_eq!(sign(0.0, NumberSign::Always), "+0");
+        assert_eq!(sign(0.0, NumberSign::ExceptZero), "0");
+        assert_eq!(sign(1.0, NumberSign::ExceptZero), "+1");
+        assert_eq!(sign(-1.0, NumberSign::Negative), "-1");
+        // Rounding to zero keeps the sign of the number
+        let rounded = |sign: NumberSign| {
+            format(
+                I18nNumber::new(-0.01)
+                    .with_max_fraction_digits(0)
+                    .with_sign(sign),
+                "en",
+            )
+        };
+        assert_eq!(rounded(NumberSign::Auto), "-0");
+        assert_eq!(rounded(NumberSign::Negative), "0");
+    }
+
+    #[test]
+    fn min_integer_digits() {
+        let digits = |value: f64| I18nNumber::new(value).with_min_integer_digits(3);
+        assert_eq!(format(digits(5.0), "en"), "005");
+        assert_eq!(format(digits(1.5), "en"), "001.5");
+        assert_eq!(format(digits(1234.0), "en"), "1,234");
+        assert_eq!(format(digits(-5.0), "de"), "-005");
+    }
+
+    #[test]
+    fn compact_notation() {
+        let short = |value: f64| I18nNumber::new(value).with_notation(NumberNotation::CompactShort);
+        let long = |value: f64| I18nNumber::new(value).with_notation(NumberNotation::CompactLong);
+        assert_eq!(format(short(999.0), "en"), "999");
+        assert_eq!(format(short(1234.0), "en"), "1.2K");
+        assert_eq!(format(short(12_345.0), "en"), "12K");
+        assert_eq!(format(short(1_234_567.0), "en"), "1.2M");
+        assert_eq!(format(short(-1234.0), "en"), "-1.2K");
+        assert_eq!(format(long(1234.0), "en"), "1.2 thousand");
+        assert_eq!(format(long(1_234_567.0), "en"), "1.2 million");
+        assert_eq!(
+            format(short(1234.0).with_sign(NumberSign::Always), "en"),
+            "+1.2K"
+        );
+    }
+
+    #[test]
+    fn compact_notation_in_japanese() {
+        let short = |value: f64| I18nNumber::new(value).with_notation(NumberNotation::CompactShort);
+        assert_eq!(format(short(1234.0), "ja"), "1234");
+        assert_eq!(format(short(123_456.0), "ja"), "12万");
+        assert_eq!(format(short(12_345.0), "ja"), "1.2万");
+        assert_eq!(format(short(123_456_789.0), "ja"), "1.2億");
+        // Compact numbers only group from 5 integer digits, `1234万`
+        assert_eq!(format(short(12_340_000.0), "ja"), "1234万");
+        assert_eq!(format(short(1e21), "ja"), "100,000京");
+        assert_eq!(
+            format(short(1e21).with_grouping(NumberGrouping::Never), "ja"),
+            "100000京"
+        );
+    }
+
+    fn scientific(value: f64) -> I18nNumber {
+        I18nNumber::new(value).with_notation(NumberNotation::Scientific)
+    }
+
+    #[test]
+    fn scientific_notation() {
+        assert_eq!(format(scientific(1234.0), "en"), "1.234E3");
+        assert_eq!(format(scientific(3.4e27), "en"), "3.4E27");
+        assert_eq!(format(scientific(0.00012), "en"), "1.2E-4");
+        assert_eq!(format(scientific(-1234.0), "en"), "-1.234E3");
+        assert_eq!(format(scientific(0.0), "en"), "0E0");
+    }
+
+    #[test]
+    fn scientific_notation_uses_the_decimal_separator_of_the_locale() {
+        assert_eq!(format(scientific(1234.0), "de"), "1,234E3");
+    }
+
+    #[test]
+    fn scientific_rounding_carries_into_the_exponent() {
+        assert_eq!(format(scientific(9999.6), "en"), "1E4");
+        assert_eq!(
+            format(scientific(1234.5).with_max_fraction_digits(1), "en"),
+            "1.2E3"
+        );
+    }
+
+    #[test]
+    fn engineering_notation() {
+        let engineering =
+            |value: f64| I18nNumber::new(value).with_notation(NumberNotation::Engineering);
+        assert_eq!(format(engineering(12345.0), "en"), "12.345E3");
+        assert_eq!(format(engineering(1_234_567.0), "en"), "1.235E6");
+        assert_eq!(format(engineering(0.012), "en"), "12E-3");
+    }
+}
@@ -0,0 +1,398 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::{
+        component::{Component, ComponentHook, HookContext, Mutable, StorageType},
+        reflect::ReflectComponent,
+    },
+    log::debug,
+    reflect::Reflect,
+    time::{Timer, TimerMode},
+    ui::widget::Text,
+};
+use fixed_decimal::{FixedDecimal, Sign};
+use icu_experimental::relativetime::{
+    options::Numeric, RelativeTimeFormatter, RelativeTimeFormatterOptions,
+};
+
+use crate::resources::I18n;
+
+use super::{utils, I18nComponent};
+
+const MINUTE: f64 = 60.0;
+const HOUR: f64 = 60.0 * MINUTE;
+const DAY: f64 = 24.0 * HOUR;
+const WEEK: f64 = 7.0 * DAY;
+/// Average length of a month of the Gregorian calendar
+const MONTH: f64 = 30.436_875 * DAY;
+const QUARTER: f64 = 3.0 * MONTH;
+/// Average length of a year of the Gregorian calendar
+const YEAR: f64 = 365.2425 * DAY;
+
+/// Unit of a relative time
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelativeTimeUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl RelativeTimeUnit {
+    fn seconds(&self) -> f64 {
+        match self {
+            Self::Second => 1.0,
+            Self::Minute => MINUTE,
+            Self::Hour => HOUR,
+            Self::Day => DAY,
+            Self::Week => WEEK,
+            Self::Month => MONTH,
+            Self::Quarter => QUARTER,
+            Self::Year => YEAR,
+        }
+    }
+
+    /// Returns the largest unit that fits in the duration, quarters are never picked
+    fn for_seconds(seconds: f64) -> Self {
+        let seconds = seconds.abs();
+        [
+            Self::Year,
+            Self::Month,
+            Self::Week,
+            Self::Day,
+            Self::Hour,
+            Self::Minute,
+        ]
+        .into_iter()
+        .find(|unit| seconds >= unit.seconds())
+        .unwrap_or(Self::Second)
+    }
+}
+
+/// Whether the CLDR relative time phrases of the locale are used, `yesterday` instead of `1 day ago`
+#[derive(Default, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelativeTimeNumeric {
+    /// Always use numbers, `1 day ago`, `in 0 days`
+    #[default]
+    Always,
+    /// Use the phrases of the locale when there is one, `yesterday`, `today`, `now`
+    Auto,
+}
+
+/// Length of the formatted relative time, following the CLDR relative time formats of the locale
+#[derive(Default, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelativeTimeLength {
+    /// `3 minutes ago`
+    #[default]
+    Long,
+    /// `3 min. ago`
+    Short,
+    /// `3m ago`
+    Narrow,
+}
+
+/// Component for spawning localized relative time entities that are managed by `bevy_simple_i18n`,
+/// `3 minutes ago`, `in 2 days`
+///
+/// It automatically inserts (or replaces) a Bevy `Text` component with the localized relative time
+///
+/// Unless a unit is set, the largest unit that fits in the duration is used,
+/// and the value is truncated to a whole number of that unit, `90 seconds ago` -> `1 minute ago`
+///
+/// Updates automatically whenever the locale is changed using the [crate::resources::I18n] resource,
+/// and with [I18nRelativeTime::with_refresh] the time keeps moving, so `now` becomes `1 minute ago`
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// # let mut world = World::new();
+/// // Basic usage, `3 minutes ago`
+/// world.spawn(I18nRelativeTime::ago(Duration::from_secs(180)));
+///
+/// // In the future, `in 2 days`
+/// world.spawn(I18nRelativeTime::from_now(Duration::from_secs(2 * 86_400)));
+///
+/// // With the phrases of the locale, `yesterday`
+/// world.spawn(I18nRelativeTime::new(-86_400).with_numeric(RelativeTimeNumeric::Auto));
+///
+/// // Refreshed every second, `0 seconds ago`, `1 second ago`, ..., `1 minute ago`
+/// world.spawn(I18nRelativeTime::new(0).with_refresh(Duration::from_secs(1)));
+///
+/// // With forced locale
+/// // overrides the global
+/// // does not update when the locale is changed
+/// world.spawn(I18nRelativeTime::new(-180).with_locale("ja"));
+/// ```
+#[derive(Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct I18nRelativeTime {
+    /// Offset from now in seconds, negative in the past
+    seconds: f64,
+    /// Unit of the formatted time, `None` to pick the largest unit that fits
+    unit: Option<RelativeTimeUnit>,
+    /// Whether the phrases of the locale are used
+    numeric: RelativeTimeNumeric,
+    /// Length of the formatted time
+    length: RelativeTimeLength,
+    /// Re-renders the relative time when it finishes, `None` to never refresh it
+    refresh: Option<Timer>,
+    /// Locale for this specific translation, `None` to use the global locale
+    pub(crate) locale: Option<String>,
+}
+
+impl I18nComponent for I18nRelativeTime {
+    fn locale(&self) -> String {
+        self.locale
+            .clone()
+            .unwrap_or(rust_i18n::locale().to_string())
+    }
+
+    fn translate(&self, i18n: &I18n) -> String {
+        self.format(i18n, &self.locale())
+    }
+}
+
+impl I18nRelativeTime {
+    /// Creates a new `I18nRelativeTime` component with the provided offset from now in seconds,
+    /// negative in the past, `-180` -> `3 minutes ago`
+    pub fn new(seconds: impl Into<f64>) -> Self {
+        Self {
+            seconds: seconds.into(),
+            unit: None,
+            numeric: RelativeTimeNumeric::default(),
+            length: RelativeTimeLength::default(),
+            refresh: None,
+            locale: None,
+        }
+    }
+
+    /// Creates a new `I18nRelativeTime` component for a time in the past, `3 minutes ago`
+    pub fn ago(duration: Duration) -> Self {
+        Self::new(-duration.as_secs_f64())
+    }
+
+    /// Creates a new `I18nRelativeTime` component for a time in the future, `in 3 minutes`
+    pub fn from_now(duration: Duration) -> Self {
+        Self::new(duration.as_secs_f64())
+    }
+
+    /// Set the unit of the formatted time instead of picking the largest unit that fits
+    pub fn with_unit(mut self, unit: RelativeTimeUnit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// Set whether the phrases of the locale are used, `yesterday` instead of `1 day ago`
+    pub fn with_numeric(mut self, numeric: RelativeTimeNumeric) -> Self {
+        self.numeric = numeric;
+        self
+    }
+
+    /// Set the length of the formatted time
+    pub fn with_length(mut self, length: RelativeTimeLength) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// Keep the time moving with the real time of the app, the text is re-rendered at every interval
+    ///
+    /// Only applies to the component, interpolation arguments are rendered once
+    pub fn with_refresh(mut self, interval: Duration) -> Self {
+        self.refresh = Some(Timer::new(interval, TimerMode::Repeating));
+        self
+    }
+
+    /// Set the locale for this specific translation
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Moves the time by the elapsed real time, returns `true` if the text needs to be re-rendered
+    pub(crate) fn tick(&mut self, delta: Duration) -> bool {
+        let Some(refresh) = self.refresh.as_mut() else {
+            return false;
+        };
+        self.seconds -= delta.as_secs_f64();
+        refresh.tick(delta).just_finished()
+    }
+
+    /// Formats the relative time with the CLDR relative time patterns of the locale
+    pub(crate) fn format(&self, i18n: &I18n, locale: &str) -> String {
+        let unit = self
+            .unit
+            .unwrap_or_else(|| RelativeTimeUnit::for_seconds(self.seconds));
+        let mut value = FixedDecimal::from((self.seconds / unit.seconds()).abs().trunc() as u64);
+        // Keeps the direction of times under one unit, `0 seconds ago`
+        if self.seconds < 0.0 {
+            value.set_sign(Sign::Negative);
+        }
+        self.formatter(&utils::cldr_locale(i18n, locale), unit)
+            .format(value)
+            .to_string()
+    }
+
+    fn formatter(&self, locale: &str, unit: RelativeTimeUnit) -> RelativeTimeFormatter {
+        use RelativeTimeLength::*;
+        use RelativeTimeUnit::*;
+
+        let label = format!("{}s", self.seconds);
+        let locale = utils::resolve_locale(locale, &label);
+        let locale_string = locale.to_string();
+        let options = RelativeTimeFormatterOptions {
+            numeric: match self.numeric {
+                RelativeTimeNumeric::Always => Numeric::Always,
+                RelativeTimeNumeric::Auto => Numeric::Auto,
+            },
+        };
+        let locale = &locale.into();
+        match (self.length, unit) {
+            (Long, Second) => RelativeTimeFormatter::try_new_long_second(locale, options),
+            (Long, Minute) => RelativeTimeFormatter::try_new_long_minute(locale, options),
+            (Long, Hour) => RelativeTimeFormatter::try_new_long_hour(locale, options),
+            (Long, Day) => RelativeTimeFormatter::try_new_long_day(locale, options),
+            (Long, Week) => RelativeTimeFormatter::try_new_long_week(locale, options),
+            (Long, Month) => RelativeTimeFormatter::try_new_long_month(locale, options),
+            (Long, Quarter) => RelativeTimeFormatter::try_new_long_quarter(locale, options),
+            (Long, Year) => RelativeTimeFormatter::try_new_long_year(locale, options),
+            (Short, Second) => RelativeTimeFormatter::try_new_short_second(locale, options),
+            (Short, Minute) => RelativeTimeFormatter::try_new_short_minute(locale, options),
+            (Short, Hour) => RelativeTimeFormatter::try_new_short_hour(locale, options),
+            (Short, Day) => RelativeTimeFormatter::try_new_short_day(locale, options),
+            (Short, Week) => RelativeTimeFormatter::try_new_short_week(locale, options),
+            (Short, Month) => RelativeTimeFormatter::try_new_short_month(locale, options),
+            (Short, Quarter) => RelativeTimeFormatter::try_new_short_quarter(locale, options),
+            (Short, Year) => RelativeTimeFormatter::try_new_short_year(locale, options),
+            (Narrow, Second) => RelativeTimeFormatter::try_new_narrow_second(locale, options),
+            (Narrow, Minute) => RelativeTimeFormatter::try_new_narrow_minute(locale, options),
+            (Narrow, Hour) => RelativeTimeFormatter::try_new_narrow_hour(locale, options),
+            (Narrow, Day) => RelativeTimeFormatter::try_new_narrow_day(locale, options),
+            (Narrow, Week) => RelativeTimeFormatter::try_new_narrow_week(locale, options),
+            (Narrow, Month) => RelativeTimeFormatter::try_new_narrow_month(locale, options),
+            (Narrow, Quarter) => RelativeTimeFormatter::try_new_narrow_quarter(locale, options),
+            (Narrow, Year) => RelativeTimeFormatter::try_new_narrow_year(locale, options),
+        }
+        .unwrap_or_else(|_| {
+            panic!(
+                "Failed to create RelativeTimeFormatter for time: {} with locale: {}",
+                label, locale_string,
+            )
+        })
+    }
+}
+
+impl Component for I18nRelativeTime {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = Mutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world, HookContext { entity, .. }| {
+            let val = world.get::<Self>(entity).unwrap().clone();
+            let translated = val.translate(world.resource::<I18n>());
+            debug!("Adding i18n relative time: {}s", val.seconds);
+            if let Some(mut text) = world.get_mut::<Text>(entity) {
+                **text = translated;
+            } else {
+                world
+                    .commands()
+                    .entity(entity)
+                    .insert(Text::new(translated));
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(relative_time: I18nRelativeTime, locale: &str) -> String {
+        relative_time.format(&I18n::default(), locale)
+    }
+
+    #[test]
+    fn largest_unit_that_fits() {
+        assert_eq!(
+            RelativeTimeUnit::for_seconds(59.0),
+            RelativeTimeUnit::Second
+        );
+        assert_eq!(
+            RelativeTimeUnit::for_seconds(60.0),
+            RelativeTimeUnit::Minute
+        );
+        assert_eq!(
+            RelativeTimeUnit::for_seconds(-90.0),
+            RelativeTimeUnit::Minute
+        );
+        assert_eq!(
+            RelativeTimeUnit::for_seconds(3.0 * DAY),
+            RelativeTimeUnit::Day
+        );
+        assert_eq!(
+            RelativeTimeUnit::for_seconds(-8.0 * DAY),
+            RelativeTimeUnit::Week
+        );
+        // Quarters are never picked
+        assert_eq!(
+            RelativeTimeUnit::for_seconds(100.0 * DAY),
+            RelativeTimeUnit::Month
+        );
+        assert_eq!(
+            RelativeTimeUnit::for_seconds(400.0 * DAY),
+            RelativeTimeUnit::Year
+        );
+    }
+
+    #[test]
+    fn values_are_truncated_in_the_direction_of_the_time() {
+        assert_eq!(format(I18nRelativeTime::new(-90), "en"), "1 minute ago");
+        assert_eq!(format(I18nRelativeTime::new(-150), "en"), "2 minutes ago");
+        assert_eq!(format(I18nRelativeTime::new(3.0 * DAY), "en"), "in 3 days");
+        assert_eq!(format(I18nRelativeTime::new(-0.5), "en"), "0 seconds ago");
+        assert_eq!(format(I18nRelativeTime::new(0), "en"), "in 0 seconds");
+        assert_eq!(
+            format(I18nRelativeTime::ago(Duration::from_secs(3 * 3600)), "en"),
+            "3 hours ago"
+        );
+    }
+
+    #[test]
+    fn units_lengths_and_phrases() {
+        assert_eq!(
+            format(
+                I18nRelativeTime::new(-90).with_unit(RelativeTimeUnit::Second),
+                "en"
+            ),
+            "90 seconds ago"
+        );
+        assert_eq!(
+            format(
+                I18nRelativeTime::new(200.0 * DAY).with_unit(RelativeTimeUnit::Quarter),
+                "en"
+            ),
+            "in 2 quarters"
+        );
+        assert_eq!(
+            format(
+                I18nRelativeTime::new(-180).with_length(RelativeTimeLength::Short),
+                "en"
+            ),
+            "3 min. ago"
+        );
+        assert_eq!(
+            format(
+                I18nRelativeTime::new(-DAY).with_numeric(RelativeTimeNumeric::Auto),
+                "en"
+            ),
+            "yesterday"
+        );
+        assert_eq!(format(I18nRelativeTime::new(-180), "ja"), "3 分前");
+    }
+}
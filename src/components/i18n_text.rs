@@ -8,10 +8,12 @@ use bevy::{
     ui::widget::Text,
 };
 
-#[cfg(feature = "numbers")]
-use fixed_decimal::FixedDecimal;
+use crate::{
+    events::{send_missing_translation, MissingKey},
+    resources::I18n,
+};
 
-use super::{utils::translate_by_key, I18nComponent};
+use super::{utils::translate_text, I18nComponent};
 
 /// Component for spawning translatable text entities that are managed by `bevy_simple_i18n`
 ///
@@ -25,17 +27,31 @@ use super::{utils::translate_by_key, I18nComponent};
 /// // en.json
 /// {
 ///     "hello": "Hello, World!",
-///     "greet": "Hello, %{name}!"
+///     "greet": "Hello, %{name}!",
+///     "found_sword": {
+///         "female": "She found a sword",
+///         "male": "He found a sword",
+///         "other": "They found a sword"
+///     }
 /// }
 /// ```
 ///
-/// ```
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_simple_i18n::prelude::*;
+/// # let mut world = World::new();
 /// // Basic usage
 /// world.spawn(I18nText::new("hello"));
 ///
 /// // With interpolation arguments
 /// world.spawn(I18nText::new("greet").with_arg("name", "Bevy User"));
 ///
+/// // With a select argument, translates `found_sword.female`
+/// world.spawn(I18nText::new("found_sword").with_select("gender", "female"));
+///
+/// // With a list argument, `Alice, Bob, and Carol`
+/// world.spawn(I18nText::new("greet").with_list_arg("names", ["Alice", "Bob", "Carol"]));
+///
 /// // With forced locale
 /// // overrides the global
 /// // does not update when the locale is changed
@@ -48,6 +64,8 @@ pub struct I18nText {
     key: String,
     /// Interpolation arguments for the translation key
     args: Vec<(String, InterpolationType)>,
+    /// Message context, selects the `msgctxt` of gettext catalogs
+    context: Option<String>,
     /// Locale for this specific translation, `None` to use the global locale
     pub(crate) locale: Option<String>,
 }
@@ -59,8 +77,18 @@ impl I18nComponent for I18nText {
             .unwrap_or(rust_i18n::locale().to_string())
     }
 
-    fn translate(&self) -> String {
-        translate_by_key(&self.locale(), &self.key, &self.args)
+    fn translate(&self, i18n: &I18n) -> String {
+        self.translate_checked(i18n).0
+    }
+
+    fn translate_checked(&self, i18n: &I18n) -> (String, Option<MissingKey>) {
+        translate_text(
+            i18n,
+            &self.locale(),
+            &self.key,
+            self.context.as_deref(),
+            &self.args,
+        )
     }
 }
 
@@ -70,6 +98,19 @@ impl I18nText {
         Self {
             key: str.into(),
             args: vec![],
+            context: None,
+            locale: None,
+        }
+    }
+
+    #[cfg(feature = "typed_keys")]
+    /// Creates a new `I18nText` component from a [Keys](crate::keys::Keys) variant generated from the locale files,
+    /// its fields are added as interpolation arguments
+    pub fn from_key(key: crate::keys::Keys) -> Self {
+        Self {
+            key: key.key().to_string(),
+            args: key.into_args(),
+            context: None,
             locale: None,
         }
     }
@@ -80,6 +121,14 @@ impl I18nText {
         self
     }
 
+    /// Set the message context, used to tell apart gettext messages that share the same `msgid`
+    ///
+    /// Matches the `msgctxt` of the message, other locale file formats ignore it
+    pub fn with_context(mut self, context: impl Into<String>) -> Self {
+        self.context = Some(context.into());
+        self
+    }
+
     /// Add a standard string interpolation argument to the translation key
     ///
     /// This method can be called as many times as needed
@@ -89,6 +138,48 @@ impl I18nText {
         self
     }
 
+    /// Add a select argument, its value picks the variant of the translation key
+    ///
+    /// `with_select("gender", "female")` translates `key.female`, falling back to `key.other` then `key`,
+    /// the value is also interpolated like a standard string argument
+    ///
+    /// This method can be called as many times as needed, variants are nested in call order
+    pub fn with_select(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.args
+            .push((key.into(), InterpolationType::Select(value.to_string())));
+        self
+    }
+
+    /// Add a list interpolation argument to the translation key,
+    /// joined with the CLDR `and` list patterns of the locale, `Alice, Bob, and Carol`
+    ///
+    /// This method can be called as many times as needed
+    #[cfg(feature = "lists")]
+    pub fn with_list_arg(
+        self,
+        key: impl Into<String>,
+        values: impl IntoIterator<Item = impl ToString>,
+    ) -> Self {
+        self.with_styled_list_arg(key, values, ListStyle::And)
+    }
+
+    /// Add a list interpolation argument to the translation key,
+    /// joined with the CLDR list patterns of the style in the locale, `Alice, Bob, or Carol`
+    ///
+    /// This method can be called as many times as needed
+    #[cfg(feature = "lists")]
+    pub fn with_styled_list_arg(
+        mut self,
+        key: impl Into<String>,
+        values: impl IntoIterator<Item = impl ToString>,
+        style: ListStyle,
+    ) -> Self {
+        let values = values.into_iter().map(|v| v.to_string()).collect();
+        self.args
+            .push((key.into(), InterpolationType::List(values, style)));
+        self
+    }
+
     #[cfg(feature = "numbers")]
     /// Add a number interpolation argument to the translation key
     ///
@@ -96,10 +187,95 @@ impl I18nText {
     pub fn with_num_arg(mut self, key: impl Into<String>, value: impl Into<f64>) -> Self {
         self.args.push((
             key.into(),
-            InterpolationType::Number(super::utils::f64_to_fd(value.into())),
+            InterpolationType::Number(super::I18nNumber::new(value)),
+        ));
+        self
+    }
+
+    #[cfg(feature = "numbers")]
+    /// Add a number interpolation argument to the translation key,
+    /// formatted with the notation of the provided [I18nNumber](super::I18nNumber) in the locale of this component
+    ///
+    /// This method can be called as many times as needed
+    pub fn with_number_arg(mut self, key: impl Into<String>, value: super::I18nNumber) -> Self {
+        self.args
+            .push((key.into(), InterpolationType::Number(value)));
+        self
+    }
+
+    #[cfg(feature = "numbers")]
+    /// Add an ordinal number interpolation argument to the translation key, `1st`, `2nd`, `3rd`
+    ///
+    /// The suffix patterns are the `ordinal.{category}` translations of the locale,
+    /// selected with the CLDR ordinal plural rules, e.g. `ordinal.few: "%{number}rd"`
+    ///
+    /// This method can be called as many times as needed
+    pub fn with_ordinal_arg(mut self, key: impl Into<String>, value: impl Into<f64>) -> Self {
+        self.args.push((
+            key.into(),
+            InterpolationType::Number(super::I18nNumber::new(value).with_ordinal()),
         ));
         self
     }
+
+    #[cfg(feature = "numbers")]
+    /// Add a currency interpolation argument to the translation key,
+    /// formatted with the display of the provided [I18nCurrency](super::I18nCurrency) in the locale of this component
+    ///
+    /// This method can be called as many times as needed
+    pub fn with_currency_arg(mut self, key: impl Into<String>, value: super::I18nCurrency) -> Self {
+        self.args
+            .push((key.into(), InterpolationType::Currency(value)));
+        self
+    }
+
+    #[cfg(feature = "numbers")]
+    /// Add a measure interpolation argument to the translation key,
+    /// formatted with the unit display of the provided [I18nMeasure](super::I18nMeasure) in the locale of this component
+    ///
+    /// This method can be called as many times as needed
+    pub fn with_measure_arg(mut self, key: impl Into<String>, value: super::I18nMeasure) -> Self {
+        self.args
+            .push((key.into(), InterpolationType::Measure(value)));
+        self
+    }
+
+    #[cfg(feature = "numbers")]
+    /// Add a relative time interpolation argument to the translation key, `3 minutes ago`,
+    /// formatted with the options of the provided [I18nRelativeTime](super::I18nRelativeTime) in the locale of this component
+    ///
+    /// This method can be called as many times as needed
+    pub fn with_relative_time_arg(
+        mut self,
+        key: impl Into<String>,
+        value: super::I18nRelativeTime,
+    ) -> Self {
+        self.args
+            .push((key.into(), InterpolationType::RelativeTime(value)));
+        self
+    }
+
+    #[cfg(feature = "numbers")]
+    /// Add a duration interpolation argument to the translation key, `1:05:33`,
+    /// formatted with the style of the provided [I18nDuration](super::I18nDuration) in the locale of this component
+    ///
+    /// This method can be called as many times as needed
+    pub fn with_duration_arg(mut self, key: impl Into<String>, value: super::I18nDuration) -> Self {
+        self.args
+            .push((key.into(), InterpolationType::Duration(value)));
+        self
+    }
+
+    #[cfg(feature = "datetime")]
+    /// Add a date and time interpolation argument to the translation key,
+    /// formatted with the lengths of the provided [I18nDateTime](super::I18nDateTime) in the locale of this component
+    ///
+    /// This method can be called as many times as needed
+    pub fn with_datetime_arg(mut self, key: impl Into<String>, value: super::I18nDateTime) -> Self {
+        self.args
+            .push((key.into(), InterpolationType::DateTime(value)));
+        self
+    }
 }
 
 impl Component for I18nText {
@@ -109,22 +285,138 @@ impl Component for I18nText {
     fn on_add() -> Option<ComponentHook> {
         Some(|mut world, HookContext { entity, .. }| {
             let val = world.get::<Self>(entity).unwrap().clone();
+            let (translated, missing) = val.translate_checked(world.resource::<I18n>());
+            send_missing_translation(&mut world.commands(), missing, entity);
             debug!("Adding i18n text: {}", val.key);
             if let Some(mut text) = world.get_mut::<Text>(entity) {
-                **text = val.translate();
+                **text = translated;
             } else {
                 world
                     .commands()
                     .entity(entity)
-                    .insert(Text::new(val.translate()));
+                    .insert(Text::new(translated));
             }
         })
     }
 }
 
+/// Style of the CLDR list patterns used to join a list argument
+#[cfg(feature = "lists")]
+#[derive(Default, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListStyle {
+    /// All of the items, `Alice, Bob, and Carol`
+    #[default]
+    And,
+    /// Any of the items, `Alice, Bob, or Carol`
+    Or,
+    /// Items of a measure, `1 hour, 5 minutes, 33 seconds`
+    Unit,
+}
+
 #[derive(Reflect, Debug, Clone)]
 pub(crate) enum InterpolationType {
     String(String),
+    /// String argument that also selects the variant of the translation key
+    Select(String),
+    /// Strings joined with the list patterns of the style
+    #[cfg(feature = "lists")]
+    List(Vec<String>, ListStyle),
+    /// Number formatted with its notation, or as an ordinal, `1st`, `2nd`, `3rd`
+    #[cfg(feature = "numbers")]
+    Number(super::I18nNumber),
+    #[cfg(feature = "numbers")]
+    Currency(super::I18nCurrency),
     #[cfg(feature = "numbers")]
-    Number(#[reflect(ignore)] FixedDecimal),
+    Measure(super::I18nMeasure),
+    #[cfg(feature = "numbers")]
+    RelativeTime(super::I18nRelativeTime),
+    #[cfg(feature = "numbers")]
+    Duration(super::I18nDuration),
+    #[cfg(feature = "datetime")]
+    DateTime(super::I18nDateTime),
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::platform::collections::HashMap;
+
+    use super::*;
+    use crate::{assets::TranslationAsset, resources::MissingTranslationPolicy};
+
+    fn i18n(loaded: bool) -> I18n {
+        let asset = TranslationAsset {
+            locales: HashMap::from_iter([(
+                "en".to_string(),
+                HashMap::from_iter([("hello".to_string(), "Hello".to_string())]),
+            )]),
+        };
+        let mut i18n = I18n::default();
+        i18n.set_translations([&asset]);
+        i18n.set_default_locale("en");
+        i18n.set_requested_locales(["en"]);
+        if loaded {
+            i18n.finish_loading();
+        }
+        i18n
+    }
+
+    fn missing(key: &str, locale: &str) -> Option<MissingKey> {
+        Some(MissingKey {
+            key: key.to_string(),
+            locale: locale.to_string(),
+        })
+    }
+
+    #[test]
+    fn translated_texts_have_no_missing_key() {
+        let text = I18nText::new("hello").with_locale("en-US");
+        assert_eq!(
+            text.translate_checked(&i18n(true)),
+            ("Hello".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn missing_keys_are_returned() {
+        let i18n = i18n(true);
+        assert_eq!(
+            I18nText::new("hello")
+                .with_locale("de")
+                .translate_checked(&i18n),
+            ("Hello".to_string(), missing("hello", "de"))
+        );
+        assert_eq!(
+            I18nText::new("bye")
+                .with_locale("en")
+                .translate_checked(&i18n),
+            ("bye".to_string(), missing("bye", "en"))
+        );
+    }
+
+    #[test]
+    fn missing_policy_applies() {
+        let mut i18n = i18n(true);
+        i18n.set_missing_policy(MissingTranslationPolicy::Marker);
+        assert_eq!(
+            I18nText::new("hello")
+                .with_locale("de")
+                .translate_checked(&i18n),
+            ("\u{27e6}hello\u{27e7}".to_string(), missing("hello", "de"))
+        );
+        i18n.set_missing_policy(MissingTranslationPolicy::Key);
+        assert_eq!(
+            I18nText::new("hello").with_locale("de").translate(&i18n),
+            "hello"
+        );
+    }
+
+    #[test]
+    fn nothing_is_missing_before_the_locale_files_are_loaded() {
+        assert_eq!(
+            I18nText::new("hello")
+                .with_locale("de")
+                .translate_checked(&i18n(false)),
+            ("Hello".to_string(), None)
+        );
+    }
 }
@@ -1,20 +1,48 @@
+#[cfg(feature = "numbers")]
+mod i18n_currency;
+#[cfg(feature = "datetime")]
+mod i18n_datetime;
+#[cfg(feature = "numbers")]
+mod i18n_duration;
 mod i18n_font;
 #[cfg(feature = "numbers")]
+mod i18n_measure;
+#[cfg(feature = "numbers")]
 mod i18n_number;
+#[cfg(feature = "numbers")]
+mod i18n_relative_time;
 mod i18n_text;
 mod i18n_text_2d;
 mod utils;
 
+#[cfg(feature = "numbers")]
+pub use i18n_currency::*;
+#[cfg(feature = "datetime")]
+pub use i18n_datetime::*;
+#[cfg(feature = "numbers")]
+pub use i18n_duration::*;
 pub use i18n_font::*;
 #[cfg(feature = "numbers")]
+pub use i18n_measure::*;
+#[cfg(feature = "numbers")]
 pub use i18n_number::*;
+#[cfg(feature = "numbers")]
+pub use i18n_relative_time::*;
 pub use i18n_text::*;
 pub use i18n_text_2d::*;
 
+use crate::{events::MissingKey, resources::I18n};
+
 pub trait I18nComponent {
     /// If set, returns the locale of the component, otherwise the global locale
     fn locale(&self) -> String;
 
-    /// Internal method that wraps the `rust_i18n::t!` macro
-    fn translate(&self) -> String;
+    /// Internal method that translates the component using the translations held by the [I18n] resource
+    fn translate(&self, i18n: &I18n) -> String;
+
+    /// Internal method that translates the component like [I18nComponent::translate],
+    /// also returning its translation key if it is missing from the language of its locale
+    fn translate_checked(&self, i18n: &I18n) -> (String, Option<MissingKey>) {
+        (self.translate(i18n), None)
+    }
 }
@@ -1,60 +1,731 @@
-use rust_i18n::t;
+use crate::{
+    events::MissingKey,
+    pseudo::{PseudoLocale, PSEUDO_SOURCE_LOCALE},
+    resources::{I18n, MissingTranslationPolicy},
+};
 
 use super::InterpolationType;
 
+/// Key of the ordinal patterns in the locale files, by CLDR ordinal plural category
+#[cfg(feature = "numbers")]
+const ORDINAL_KEY: &str = "ordinal";
+
 #[cfg(feature = "numbers")]
 pub(super) fn f64_to_fd(value: f64) -> fixed_decimal::FixedDecimal {
     fixed_decimal::FixedDecimal::try_from_f64(value, fixed_decimal::FloatPrecision::Floating)
-        .expect(format!("Failed to parse FixedDecimal from f64: {}", value).as_str())
+        .unwrap_or_else(|_| panic!("Failed to parse FixedDecimal from f64: {}", value))
 }
 
-#[cfg(feature = "numbers")]
-pub(super) fn resolve_locale(locale: &String, label: impl ToString) -> icu_locid::Locale {
+#[cfg(feature = "lists")]
+pub(super) fn resolve_locale(locale: &str, label: impl ToString) -> icu_locid::Locale {
     locale
         .parse()
-        .expect(format!("Invalid locale: {} for key: {}", locale, label.to_string()).as_str())
+        .unwrap_or_else(|_| panic!("Invalid locale: {} for key: {}", locale, label.to_string()))
+}
+
+/// Returns the locale used to format numbers, dates and lists,
+/// the first locale of the [I18n::locale_chain] that has CLDR data, or the locale itself if none has
+///
+/// `tlh` -> `en` with `en` as the default locale, instead of the root CLDR patterns
+#[cfg(any(feature = "lists", feature = "datetime"))]
+pub(super) fn cldr_locale(i18n: &I18n, locale: &str) -> String {
+    i18n.locale_chain(locale)
+        .into_iter()
+        .find(|candidate| has_cldr_data(candidate))
+        .unwrap_or_else(|| locale.to_string())
+}
+
+/// Unsupported locales resolve to the root locale, `und`
+///
+/// The list patterns are checked when the `lists` feature is enabled, which `numbers` enables as well,
+/// otherwise the date patterns
+#[cfg(any(feature = "lists", feature = "datetime"))]
+fn has_cldr_data(locale: &str) -> bool {
+    use icu_provider::{DataProvider, DataRequest};
+
+    let Ok(locale) = locale.parse::<icu_locid::Locale>() else {
+        return false;
+    };
+    let request = DataRequest {
+        locale: &(&locale).into(),
+        metadata: Default::default(),
+    };
+    #[cfg(feature = "lists")]
+    let response = DataProvider::<icu_list::provider::AndListV1Marker>::load(
+        &icu_list::provider::Baked,
+        request,
+    );
+    #[cfg(not(feature = "lists"))]
+    let response =
+        DataProvider::<icu_datetime::provider::calendar::GregorianDateLengthsV1Marker>::load(
+            &icu_datetime::provider::Baked,
+            request,
+        );
+    response.is_ok_and(|response| {
+        response
+            .metadata
+            .locale
+            .is_none_or(|resolved| !resolved.is_und())
+    })
 }
 
 #[cfg(feature = "numbers")]
 pub(super) fn get_formatter(
-    locale: &String,
+    locale: &str,
+    label: impl ToString,
+) -> icu_decimal::FixedDecimalFormatter {
+    get_formatter_with_options(locale, label, Default::default())
+}
+
+#[cfg(feature = "numbers")]
+pub(super) fn get_formatter_with_options(
+    locale: &str,
     label: impl ToString,
+    options: icu_decimal::options::FixedDecimalFormatterOptions,
 ) -> icu_decimal::FixedDecimalFormatter {
     let label_string = label.to_string();
     let locale = resolve_locale(locale, label);
     let locale_string = locale.to_string();
-    icu_decimal::FixedDecimalFormatter::try_new(&locale.into(), Default::default()).expect(
-        format!(
+    icu_decimal::FixedDecimalFormatter::try_new(&locale.into(), options).unwrap_or_else(|_| {
+        panic!(
             "Failed to create FixedDecimalFormatter for number: {} with locale: {}",
             label_string, locale_string,
         )
-        .as_str(),
-    )
+    })
+}
+
+#[cfg(feature = "numbers")]
+pub(super) fn get_compact_formatter(
+    locale: &str,
+    label: impl ToString,
+    long: bool,
+    options: icu_decimal::options::FixedDecimalFormatterOptions,
+) -> icu_experimental::compactdecimal::CompactDecimalFormatter {
+    use icu_experimental::compactdecimal::CompactDecimalFormatter;
+
+    let label_string = label.to_string();
+    let locale = resolve_locale(locale, label);
+    let locale_string = locale.to_string();
+    let formatter = if long {
+        CompactDecimalFormatter::try_new_long(&locale.into(), options.into())
+    } else {
+        CompactDecimalFormatter::try_new_short(&locale.into(), options.into())
+    };
+    formatter.unwrap_or_else(|_| {
+        panic!(
+            "Failed to create CompactDecimalFormatter for number: {} with locale: {}",
+            label_string, locale_string,
+        )
+    })
+}
+
+#[cfg(feature = "lists")]
+pub(super) fn get_list_formatter(
+    locale: &str,
+    label: impl ToString,
+    style: super::ListStyle,
+    length: icu_list::ListLength,
+) -> icu_list::ListFormatter {
+    use icu_list::ListFormatter;
+
+    let label_string = label.to_string();
+    let locale = resolve_locale(locale, label);
+    let locale_string = locale.to_string();
+    let locale = &locale.into();
+    match style {
+        super::ListStyle::And => ListFormatter::try_new_and_with_length(locale, length),
+        super::ListStyle::Or => ListFormatter::try_new_or_with_length(locale, length),
+        super::ListStyle::Unit => ListFormatter::try_new_unit_with_length(locale, length),
+    }
+    .unwrap_or_else(|_| {
+        panic!(
+            "Failed to create ListFormatter for list: {} with locale: {}",
+            label_string, locale_string,
+        )
+    })
+}
+
+#[cfg(feature = "datetime")]
+pub(super) fn get_datetime_formatter(
+    locale: &icu_locid::Locale,
+    options: icu_datetime::options::length::Bag,
+) -> Result<icu_datetime::DateTimeFormatter, icu_datetime::DateTimeError> {
+    icu_datetime::DateTimeFormatter::try_new(&locale.into(), options.into())
+}
+
+/// Formats the number as an ordinal, `1st`, `2nd`, `3rd`
+///
+/// The pattern is the `ordinal` translation of the CLDR ordinal plural category of the number,
+/// e.g. `ordinal.two: "%{number}nd"`, falling back to `ordinal.other`, `ordinal`, then to the number itself
+#[cfg(feature = "numbers")]
+pub(super) fn format_ordinal(
+    i18n: &I18n,
+    locale: &str,
+    fdf: &icu_decimal::FixedDecimalFormatter,
+    number: &fixed_decimal::FixedDecimal,
+) -> String {
+    let formatted = fdf.format_to_string(number);
+    for candidate in i18n.locale_chain(locale) {
+        let Some(category) =
+            plural_category(&candidate, number, icu_plurals::PluralRuleType::Ordinal)
+        else {
+            continue;
+        };
+        let keys = [
+            format!("{}.{}", ORDINAL_KEY, category),
+            format!("{}.other", ORDINAL_KEY),
+            ORDINAL_KEY.to_string(),
+        ];
+        for key in keys {
+            if let Some(pattern) = i18n.exact_translation(&candidate, &key) {
+                return rust_i18n::replace_patterns(pattern, &["number"], &[formatted]);
+            }
+        }
+    }
+    bevy::log::debug!("Missing ordinal patterns for locale: {}", locale);
+    formatted
 }
 
+/// Looks up the key in the translations of the [I18n] resource and replaces the interpolation arguments
+///
+/// Every locale of the chain is checked in order, `en-US` -> `en`,
+/// and with the `fluent`/`gettext` features Fluent messages and gettext catalogs are checked for each of them as well
+///
+/// Select arguments pick the variant of the key first, `dialogue.found.female` then `dialogue.found.other`,
+/// and with a count argument and the `numbers` feature, the plural variants of the key are checked next,
+/// `messages.cats.few` then `messages.cats.other`, selected with the CLDR plural rules of the locale
+///
+/// The `context` is only used by gettext catalogs, where it selects the `msgctxt` of the message
+///
+/// Returns the key itself if no translation was found
+#[cfg(feature = "numbers")]
 pub(super) fn translate_by_key(
-    locale: &String,
-    key: &String,
-    args: &Vec<(String, InterpolationType)>,
+    i18n: &I18n,
+    locale: &str,
+    key: &str,
+    context: Option<&str>,
+    args: &[(String, InterpolationType)],
 ) -> String {
-    let key = key.as_str();
+    find_translation(i18n, locale, key, context, args).map_or_else(
+        || {
+            bevy::log::debug!("Missing translation for key: {} in locale: {}", key, locale);
+            key.to_string()
+        },
+        |(translated, _)| translated,
+    )
+}
 
-    #[cfg(feature = "numbers")]
-    let fdf = super::utils::get_formatter(locale, key);
+/// Translates the key of a text component like [translate_by_key]
+///
+/// If no locale of the language of the locale has a translation for the key,
+/// it is returned as a [MissingKey], to be sent as a [crate::events::MissingTranslation],
+/// and the [MissingTranslationPolicy] of the [I18n] resource applies
+pub(super) fn translate_text(
+    i18n: &I18n,
+    locale: &str,
+    key: &str,
+    context: Option<&str>,
+    args: &[(String, InterpolationType)],
+) -> (String, Option<MissingKey>) {
+    let found = find_translation(i18n, locale, key, context, args);
+    let language = |locale: &str| {
+        locale
+            .parse::<icu_locid::Locale>()
+            .map(|locale| locale.id.language)
+            .ok()
+    };
+    let fallback = match found {
+        Some((translated, candidate)) if language(&candidate) == language(locale) => {
+            return (translated, None);
+        }
+        Some((translated, _)) => translated,
+        None => key.to_string(),
+    };
+    // Texts spawned before the locale files are loaded are translated again once they are
+    if !i18n.is_loaded() {
+        return (fallback, None);
+    }
 
+    bevy::log::debug!("Missing translation for key: {} in locale: {}", key, locale);
+    let missing = MissingKey {
+        key: key.to_string(),
+        locale: locale.to_string(),
+    };
+    let translated = match i18n.missing_policy() {
+        MissingTranslationPolicy::Fallback => fallback,
+        MissingTranslationPolicy::Key => key.to_string(),
+        MissingTranslationPolicy::Marker => format!("\u{27e6}{}\u{27e7}", key),
+        MissingTranslationPolicy::Panic => {
+            if cfg!(debug_assertions) {
+                panic!("Missing translation for key: {} in locale: {}", key, locale);
+            }
+            fallback
+        }
+    };
+    (translated, Some(missing))
+}
+
+/// Looks up the key like [translate_by_key], returns the translation and the locale of the chain that has it
+fn find_translation(
+    i18n: &I18n,
+    locale: &str,
+    key: &str,
+    context: Option<&str>,
+    args: &[(String, InterpolationType)],
+) -> Option<(String, String)> {
     let (patterns, values): (Vec<&str>, Vec<String>) = args
         .iter()
         .map(|(k, interpolation_type)| {
             let value = match interpolation_type {
-                InterpolationType::String(v) => v.clone(),
+                InterpolationType::String(v) | InterpolationType::Select(v) => v.clone(),
+                #[cfg(feature = "lists")]
+                InterpolationType::List(v, style) => get_list_formatter(
+                    &cldr_locale(i18n, locale),
+                    key,
+                    *style,
+                    icu_list::ListLength::Wide,
+                )
+                .format_to_string(v.iter()),
+                #[cfg(feature = "numbers")]
+                InterpolationType::Number(v) => v.format(i18n, locale),
                 #[cfg(feature = "numbers")]
-                InterpolationType::Number(v) => fdf.format_to_string(v),
+                InterpolationType::Currency(v) => v.format(i18n, locale),
+                #[cfg(feature = "numbers")]
+                InterpolationType::Measure(v) => v.format(i18n, locale),
+                #[cfg(feature = "numbers")]
+                InterpolationType::RelativeTime(v) => v.format(i18n, locale),
+                #[cfg(feature = "numbers")]
+                InterpolationType::Duration(v) => v.format(i18n, locale),
+                #[cfg(feature = "datetime")]
+                InterpolationType::DateTime(v) => v.format(i18n, locale),
             };
             (k.as_str(), value)
         })
         .unzip();
-    let translated = t!(key, locale = locale);
 
-    let val = rust_i18n::replace_patterns(&translated, patterns.as_slice(), values.as_slice());
-    val
+    #[cfg(feature = "fluent")]
+    let fluent_args = to_fluent_args(args, &values);
+    #[cfg(feature = "numbers")]
+    let plural_operand = plural_operand(args);
+    #[cfg(feature = "gettext")]
+    let count = gettext_count(args);
+    #[cfg(not(feature = "gettext"))]
+    let _ = context;
+
+    let keys = select_keys(key, args);
+
+    // Pseudo-locales are generated from the translations of the default locale
+    let pseudo = PseudoLocale::from_locale(locale);
+    let source_locale = match pseudo {
+        Some(_) => i18n.default_locale().unwrap_or(PSEUDO_SOURCE_LOCALE),
+        None => locale,
+    };
+    let pseudolocalize = |translated: &str| match pseudo {
+        Some(pseudo) => pseudo.apply(translated, i18n.pseudo_options()),
+        None => translated.to_string(),
+    };
+    let render = |translated: &str| {
+        rust_i18n::replace_patterns(
+            &pseudolocalize(translated),
+            patterns.as_slice(),
+            values.as_slice(),
+        )
+    };
+    let found = |translated: String, candidate: String| match pseudo {
+        Some(_) => Some((translated, locale.to_string())),
+        None => Some((translated, candidate)),
+    };
+
+    for candidate in i18n.locale_chain(source_locale) {
+        for key in &keys {
+            // Plural variants of the key, `messages.cats.few` -> `messages.cats.other`
+            #[cfg(feature = "numbers")]
+            if let Some(category) = plural_operand.as_ref().and_then(|count| {
+                plural_category(&candidate, count, icu_plurals::PluralRuleType::Cardinal)
+            }) {
+                for category in [category, "other"] {
+                    let plural_key = format!("{}.{}", key, category);
+                    if let Some(translated) = i18n.exact_translation(&candidate, &plural_key) {
+                        return found(render(translated), candidate);
+                    }
+                }
+            }
+            if let Some(translated) = i18n.exact_translation(&candidate, key) {
+                return found(render(translated), candidate);
+            }
+        }
+        #[cfg(feature = "fluent")]
+        if let Some(translated) = i18n.fluent().format(&candidate, key, &fluent_args) {
+            return found(pseudolocalize(&translated), candidate);
+        }
+        #[cfg(feature = "gettext")]
+        if let Some(translated) = i18n.gettext().get(&candidate, context, key, count) {
+            return found(render(translated), candidate);
+        }
+    }
+
+    None
+}
+
+/// Returns the keys to look up in order, every select argument picks its variant then the `other` variant,
+/// `dialogue.found` with `gender: female` -> `dialogue.found.female`, `dialogue.found.other`, `dialogue.found`
+fn select_keys(key: &str, args: &[(String, InterpolationType)]) -> Vec<String> {
+    let mut keys = vec![key.to_string()];
+    for (_, interpolation_type) in args {
+        if let InterpolationType::Select(value) = interpolation_type {
+            keys = keys
+                .iter()
+                .flat_map(|key| [format!("{}.{}", key, value), format!("{}.other", key)])
+                .collect();
+            keys.dedup();
+        }
+    }
+    if keys.first().map(String::as_str) != Some(key) {
+        keys.push(key.to_string());
+    }
+    keys
+}
+
+/// Maps the interpolation arguments to Fluent arguments,
+/// numbers are kept as numbers so they can be used by Fluent selectors, other arguments are passed formatted
+#[cfg(feature = "fluent")]
+fn to_fluent_args<'a>(
+    args: &'a [(String, InterpolationType)],
+    values: &'a [String],
+) -> fluent_bundle::FluentArgs<'a> {
+    let mut fluent_args = fluent_bundle::FluentArgs::with_capacity(args.len());
+    for ((key, interpolation_type), formatted) in args.iter().zip(values) {
+        let value = match interpolation_type {
+            #[cfg(feature = "numbers")]
+            InterpolationType::Number(v) => {
+                fluent_bundle::FluentValue::try_number(&v.fixed_decimal.to_string()).into_owned()
+            }
+            _ => fluent_bundle::FluentValue::from(formatted.as_str()),
+        };
+        fluent_args.set(key.as_str(), value);
+    }
+    fluent_args
+}
+
+/// Returns the argument used to select plural forms,
+/// the `count` argument if there is one, otherwise the first number argument
+#[cfg(any(feature = "numbers", feature = "gettext"))]
+fn count_arg(args: &[(String, InterpolationType)]) -> Option<&InterpolationType> {
+    args.iter()
+        .find(|(key, _)| key == "count")
+        .or_else(|| {
+            args.iter().find(|(_, v)| match v {
+                InterpolationType::String(_) | InterpolationType::Select(_) => false,
+                #[cfg(feature = "lists")]
+                InterpolationType::List(..) => false,
+                #[cfg(feature = "numbers")]
+                InterpolationType::Number(v) => !v.ordinal,
+                #[cfg(feature = "numbers")]
+                InterpolationType::Currency(_)
+                | InterpolationType::Measure(_)
+                | InterpolationType::RelativeTime(_)
+                | InterpolationType::Duration(_) => false,
+                #[cfg(feature = "datetime")]
+                InterpolationType::DateTime(_) => false,
+            })
+        })
+        .map(|(_, v)| v)
+}
+
+/// Returns the count of the gettext plural forms, which only support non negative integers
+#[cfg(feature = "gettext")]
+fn gettext_count(args: &[(String, InterpolationType)]) -> Option<u64> {
+    let count = match count_arg(args)? {
+        InterpolationType::String(v) | InterpolationType::Select(v) => {
+            v.trim().parse::<f64>().ok()?
+        }
+        #[cfg(feature = "lists")]
+        InterpolationType::List(..) => return None,
+        #[cfg(feature = "numbers")]
+        InterpolationType::Number(v) => v.fixed_decimal.to_string().parse::<f64>().ok()?,
+        #[cfg(feature = "numbers")]
+        InterpolationType::Currency(v) => v.fixed_decimal.to_string().parse::<f64>().ok()?,
+        #[cfg(feature = "numbers")]
+        InterpolationType::Measure(_)
+        | InterpolationType::RelativeTime(_)
+        | InterpolationType::Duration(_) => return None,
+        #[cfg(feature = "datetime")]
+        InterpolationType::DateTime(_) => return None,
+    };
+    Some(count.abs().trunc() as u64)
+}
+
+/// Returns the count as a decimal, keeping the fraction digits that CLDR plural rules depend on
+#[cfg(feature = "numbers")]
+fn plural_operand(args: &[(String, InterpolationType)]) -> Option<fixed_decimal::FixedDecimal> {
+    match count_arg(args)? {
+        InterpolationType::String(v) | InterpolationType::Select(v) => v.trim().parse().ok(),
+        #[cfg(feature = "lists")]
+        InterpolationType::List(..) => None,
+        InterpolationType::Number(v) => Some(v.plural_operand()),
+        InterpolationType::Currency(v) => Some(v.fixed_decimal.clone()),
+        InterpolationType::Measure(_)
+        | InterpolationType::RelativeTime(_)
+        | InterpolationType::Duration(_) => None,
+        #[cfg(feature = "datetime")]
+        InterpolationType::DateTime(_) => None,
+    }
+}
+
+/// Returns the CLDR plural category of the count in the locale, e.g. `one`, `few` or `other`
+#[cfg(feature = "numbers")]
+fn plural_category(
+    locale: &str,
+    count: &fixed_decimal::FixedDecimal,
+    rule_type: icu_plurals::PluralRuleType,
+) -> Option<&'static str> {
+    use icu_plurals::{PluralCategory, PluralRules};
+
+    let locale = locale.parse::<icu_locid::Locale>().ok()?;
+    let rules = PluralRules::try_new(&locale.into(), rule_type).ok()?;
+    Some(match rules.category_for(count) {
+        PluralCategory::Zero => "zero",
+        PluralCategory::One => "one",
+        PluralCategory::Two => "two",
+        PluralCategory::Few => "few",
+        PluralCategory::Many => "many",
+        PluralCategory::Other => "other",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::platform::collections::HashMap;
+
+    use super::*;
+    use crate::assets::TranslationAsset;
+
+    fn i18n(translations: &[(&str, &str)]) -> I18n {
+        let asset = TranslationAsset {
+            locales: HashMap::from_iter([(
+                "en".to_string(),
+                translations
+                    .iter()
+                    .map(|(key, text)| (key.to_string(), text.to_string()))
+                    .collect(),
+            )]),
+        };
+        let mut i18n = I18n::default();
+        i18n.set_translations([&asset]);
+        i18n
+    }
+
+    fn select(name: &str, value: &str) -> (String, InterpolationType) {
+        (
+            name.to_string(),
+            InterpolationType::Select(value.to_string()),
+        )
+    }
+
+    fn translate(i18n: &I18n, key: &str, args: &[(String, InterpolationType)]) -> Option<String> {
+        find_translation(i18n, "en", key, None, args).map(|(translated, _)| translated)
+    }
+
+    #[test]
+    fn select_keys_fall_back_to_other_then_the_key() {
+        assert_eq!(
+            select_keys("found", &[select("gender", "female")]),
+            ["found.female", "found.other", "found"]
+        );
+        assert_eq!(
+            select_keys("found", &[select("gender", "other")]),
+            ["found.other", "found"]
+        );
+        assert_eq!(
+            select_keys(
+                "found",
+                &[select("gender", "female"), select("formality", "formal")]
+            ),
+            [
+                "found.female.formal",
+                "found.female.other",
+                "found.other.formal",
+                "found.other.other",
+                "found"
+            ]
+        );
+        assert_eq!(select_keys("found", &[]), ["found"]);
+    }
+
+    #[test]
+    fn select_variants() {
+        let i18n = i18n(&[
+            ("found.female", "She found it"),
+            ("found.other", "They found it"),
+            ("title", "Title"),
+        ]);
+        let translate =
+            |key: &str, gender: &str| translate(&i18n, key, &[select("gender", gender)]);
+
+        assert_eq!(
+            translate("found", "female").as_deref(),
+            Some("She found it")
+        );
+        assert_eq!(translate("found", "male").as_deref(), Some("They found it"));
+        // Keys without variants are translated as is
+        assert_eq!(translate("title", "female").as_deref(), Some("Title"));
+        assert_eq!(translate("missing", "female"), None);
+    }
+
+    #[cfg(feature = "numbers")]
+    #[test]
+    fn select_variants_with_plural_forms() {
+        let i18n = i18n(&[
+            ("found.female.one", "She found %{count} sword"),
+            ("found.female.other", "She found %{count} swords"),
+            ("found.other.other", "They found %{count} swords"),
+        ]);
+        let translate = |gender: &str, count: f64| {
+            let count = (
+                "count".to_string(),
+                InterpolationType::Number(super::super::I18nNumber::new(count)),
+            );
+            translate(&i18n, "found", &[select("gender", gender), count])
+        };
+
+        assert_eq!(
+            translate("female", 1.0).as_deref(),
+            Some("She found 1 sword")
+        );
+        assert_eq!(
+            translate("female", 2.0).as_deref(),
+            Some("She found 2 swords")
+        );
+        assert_eq!(
+            translate("male", 1.0).as_deref(),
+            Some("They found 1 swords")
+        );
+    }
+
+    #[cfg(feature = "lists")]
+    #[test]
+    fn list_styles() {
+        use super::super::ListStyle;
+
+        let join = |locale: &str, style: ListStyle, values: &[&str]| {
+            get_list_formatter(locale, "list", style, icu_list::ListLength::Wide)
+                .format_to_string(values.iter())
+        };
+        let values = ["A", "B", "C"];
+
+        assert_eq!(join("en", ListStyle::And, &values), "A, B, and C");
+        assert_eq!(join("es", ListStyle::And, &values), "A, B y C");
+        assert_eq!(join("ja", ListStyle::And, &values), "A、B、C");
+        assert_eq!(join("en", ListStyle::Or, &values), "A, B, or C");
+        assert_eq!(join("es", ListStyle::Or, &values), "A, B o C");
+        assert_eq!(join("ja", ListStyle::Or, &values), "A、B、またはC");
+        assert_eq!(join("en", ListStyle::Unit, &values), "A, B, C");
+        assert_eq!(join("es", ListStyle::Unit, &values), "A, B y C");
+        assert_eq!(join("ja", ListStyle::Unit, &values), "A B C");
+        assert_eq!(join("en", ListStyle::And, &["A", "B"]), "A and B");
+        assert_eq!(join("en", ListStyle::And, &["A"]), "A");
+    }
+
+    #[cfg(feature = "lists")]
+    #[test]
+    fn list_arguments() {
+        let i18n = i18n(&[("joined", "%{names} joined")]);
+        let list = |style| {
+            (
+                "names".to_string(),
+                InterpolationType::List(vec!["Alice".to_string(), "Bob".to_string()], style),
+            )
+        };
+
+        assert_eq!(
+            translate(&i18n, "joined", &[list(super::super::ListStyle::And)]).as_deref(),
+            Some("Alice and Bob joined")
+        );
+        assert_eq!(
+            translate(&i18n, "joined", &[list(super::super::ListStyle::Or)]).as_deref(),
+            Some("Alice or Bob joined")
+        );
+    }
+
+    #[cfg(feature = "numbers")]
+    #[test]
+    fn ordinal_categories() {
+        let i18n = i18n(&[
+            ("ordinal.one", "%{number}st"),
+            ("ordinal.two", "%{number}nd"),
+            ("ordinal.few", "%{number}rd"),
+            ("ordinal.other", "%{number}th"),
+        ]);
+        let fdf = get_formatter("en", "ordinal");
+        let ordinal = |number: f64| format_ordinal(&i18n, "en", &fdf, &f64_to_fd(number));
+
+        assert_eq!(ordinal(1.0), "1st");
+        assert_eq!(ordinal(2.0), "2nd");
+        assert_eq!(ordinal(3.0), "3rd");
+        assert_eq!(ordinal(4.0), "4th");
+        assert_eq!(ordinal(11.0), "11th");
+        assert_eq!(ordinal(12.0), "12th");
+        assert_eq!(ordinal(13.0), "13th");
+        assert_eq!(ordinal(21.0), "21st");
+        assert_eq!(ordinal(22.0), "22nd");
+        assert_eq!(ordinal(101.0), "101st");
+    }
+
+    #[cfg(feature = "numbers")]
+    #[test]
+    fn ordinal_keys_fall_back_to_other_then_the_key() {
+        let fdf = get_formatter("en", "ordinal");
+        let ordinal =
+            |i18n: &I18n, number: f64| format_ordinal(i18n, "en", &fdf, &f64_to_fd(number));
+
+        let other = i18n(&[
+            ("ordinal.one", "%{number}st"),
+            ("ordinal.other", "%{number}th"),
+        ]);
+        assert_eq!(ordinal(&other, 1.0), "1st");
+        assert_eq!(ordinal(&other, 2.0), "2th");
+
+        let key = i18n(&[("ordinal", "#%{number}")]);
+        assert_eq!(ordinal(&key, 2.0), "#2");
+
+        // Without any pattern the number is shown as is
+        assert_eq!(ordinal(&i18n(&[]), 1200.0), "1,200");
+    }
+
+    #[cfg(all(feature = "fluent", feature = "numbers"))]
+    #[test]
+    fn fluent_arguments_and_selectors() {
+        use std::sync::Arc;
+
+        use crate::assets::FluentAsset;
+
+        let asset = FluentAsset {
+            locale: "en".to_string(),
+            resource: Arc::new(
+                fluent_bundle::FluentResource::try_new(
+                    "cats = { $count ->\n    [one] { $name } has one cat\n   *[other] { $name } has { $count } cats\n}\n"
+                        .to_string(),
+                )
+                .unwrap(),
+            ),
+        };
+        let mut i18n = I18n::default();
+        i18n.set_fluent_resources([&asset]);
+        let translate = |count: f64| {
+            let args = [
+                (
+                    "name".to_string(),
+                    InterpolationType::String("Alice".to_string()),
+                ),
+                (
+                    "count".to_string(),
+                    InterpolationType::Number(super::super::I18nNumber::new(count)),
+                ),
+            ];
+            translate(&i18n, "cats", &args)
+        };
+
+        assert_eq!(translate(1.0).as_deref(), Some("Alice has one cat"));
+        assert_eq!(translate(1200.0).as_deref(), Some("Alice has 1200 cats"));
+    }
 }
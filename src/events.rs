@@ -0,0 +1,54 @@
+use bevy::ecs::{entity::Entity, event::Event, system::Commands};
+
+/// Sent when a text has no translation in the language of its locale,
+/// both as a buffered event and as an observer trigger targeting the entity of the text
+///
+/// The shown text depends on the [crate::resources::MissingTranslationPolicy] of the [crate::resources::I18n] resource
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_simple_i18n::prelude::*;
+///
+/// fn log_missing_translations(mut events: EventReader<MissingTranslation>) {
+///     for event in events.read() {
+///         warn!("Missing translation for {} in {}", event.key, event.locale);
+///     }
+/// }
+/// ```
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct MissingTranslation {
+    /// Translation key of the text
+    pub key: String,
+    /// Locale of the text
+    pub locale: String,
+    /// Entity of the text
+    pub entity: Entity,
+}
+
+/// Key and locale of a translation missing from a text, see [crate::prelude::I18nComponent::translate_checked]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingKey {
+    /// Translation key of the text
+    pub key: String,
+    /// Locale of the text
+    pub locale: String,
+}
+
+/// Sends the missing translation of the text of the entity, if any
+pub(crate) fn send_missing_translation(
+    commands: &mut Commands,
+    missing: Option<MissingKey>,
+    entity: Entity,
+) {
+    let Some(MissingKey { key, locale }) = missing else {
+        return;
+    };
+    let event = MissingTranslation {
+        key,
+        locale,
+        entity,
+    };
+    commands.send_event(event.clone());
+    commands.trigger_targets(event, entity);
+}
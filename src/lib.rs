@@ -1,11 +1,55 @@
+mod assets;
 mod components;
+mod events;
 mod plugin;
+mod pseudo;
 mod resources;
+mod storage;
 
 include!(concat!(env!("OUT_DIR"), "/bevy_simple_i18n.rs"));
 
+#[doc(hidden)]
+pub use bevy_simple_i18n_macros::i18n_key_error as __i18n_key_error;
+
+/// Keys of plural forms and select variants are checked without their suffix
+///
+/// ```
+/// use bevy_simple_i18n::i18n_key;
+///
+/// assert_eq!(i18n_key!("messages.cats"), "messages.cats");
+/// assert_eq!(i18n_key!("dialogue.found_sword"), "dialogue.found_sword");
+/// ```
+///
+/// but the prefixes of the keys can't be translated
+///
+/// ```compile_fail
+/// use bevy_simple_i18n::i18n_key;
+///
+/// let key = i18n_key!("messages");
+/// ```
+///
+/// ```compile_fail
+/// use bevy_simple_i18n::i18n_key;
+///
+/// let key = i18n_key!("dialogue");
+/// ```
+///
+/// ```compile_fail
+/// use bevy_simple_i18n::i18n_key;
+///
+/// let key = i18n_key!("currency");
+/// ```
+#[cfg(doctest)]
+pub struct I18nKeyPrefixes;
+
 pub mod prelude {
+    pub use crate::assets::*;
     pub use crate::components::*;
+    pub use crate::events::*;
+    #[cfg(feature = "typed_keys")]
+    pub use crate::keys::Keys;
     pub use crate::plugin::*;
+    pub use crate::pseudo::*;
     pub use crate::resources::*;
+    pub use crate::storage::*;
 }
@@ -2,30 +2,51 @@ use std::path::Path;
 
 use bevy::{
     app::{App, Plugin, PreStartup, Update},
-    asset::{AssetServer, Handle},
+    asset::{Asset, AssetApp, AssetEvent, AssetServer, Assets, Handle},
     ecs::{
         component::Component,
+        entity::Entity,
+        event::EventReader,
+        query::AnyOf,
         schedule::{
             common_conditions::{resource_changed, resource_exists, resource_removed},
-            IntoScheduleConfigs,
+            IntoScheduleConfigs, SystemSet,
         },
         system::{Commands, Query, Res, ResMut},
     },
-    text::{Font, TextFont},
+    text::{Font, Text2d, TextFont},
     ui::widget::Text,
 };
+#[cfg(feature = "numbers")]
+use bevy::{
+    ecs::change_detection::DetectChangesMut,
+    time::{Real, Time, Virtual},
+};
 
+#[cfg(feature = "fluent")]
+use crate::assets::{FluentAsset, FluentAssetLoader};
+#[cfg(feature = "gettext")]
+use crate::assets::{GettextAsset, GettextAssetLoader};
+#[cfg(feature = "datetime")]
+use crate::components::I18nDateTime;
+#[cfg(feature = "numbers")]
+use crate::components::{I18nCurrency, I18nDuration, I18nMeasure, I18nNumber, I18nRelativeTime};
 use crate::{
-    components::{I18nFont, I18nNumber, I18nText},
+    assets::{TranslationAsset, TranslationAssetLoader},
+    components::{I18nFont, I18nText},
+    events::{send_missing_translation, MissingTranslation},
     prelude::{I18nComponent, I18nText2d},
-    resources::{FontFolder, FontManager, FontsLoading, I18n},
-    FONT_FAMILIES,
+    resources::{FontFolder, FontManager, FontsLoading, I18n, TranslationManager},
+    FONT_FAMILIES, TRANSLATION_FILES,
 };
 
 /// Initializes the `bevy_simple_i18n` plugin
 ///
+/// The locale files found in the assets folder by the build script are loaded as [TranslationAsset]s
+/// through the [AssetServer], so they can be patched without recompiling the game
+///
 /// # Example
-/// ```
+/// ```no_run
 /// use bevy::prelude::*;
 /// use bevy_simple_i18n::prelude::*;
 ///
@@ -37,19 +58,60 @@ use crate::{
 /// ```
 pub struct I18nPlugin;
 
+/// System sets of the `bevy_simple_i18n` plugin, in the `Update` schedule
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum I18nSet {
+    /// Re-renders the live [I18nRelativeTime] and [I18nDuration] components,
+    /// systems that update them should run `.before(I18nSet::Live)` so the new value is shown the same frame
+    Live,
+}
+
 impl Plugin for I18nPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.init_resource::<I18n>()
+        app.init_asset::<TranslationAsset>()
+            .init_asset_loader::<TranslationAssetLoader>()
+            .init_resource::<I18n>()
+            .init_resource::<TranslationManager>()
             .init_resource::<FontManager>()
             .init_resource::<FontsLoading>()
-            .add_systems(PreStartup, load_dynamic_fonts)
+            .add_event::<MissingTranslation>()
+            .add_systems(PreStartup, (load_translations, load_dynamic_fonts))
             .register_i18n_component::<I18nText>()
             .register_i18n_component::<I18nText2d>()
-            .register_i18n_component::<I18nNumber>()
             .add_systems(
                 Update,
-                monitor_font_loading.run_if(resource_exists::<FontsLoading>),
+                (
+                    update_translations,
+                    finish_loading
+                        .after(update_translations)
+                        .run_if(|i18n: Res<I18n>| !i18n.is_loaded()),
+                    monitor_font_loading.run_if(resource_exists::<FontsLoading>),
+                ),
+            );
+
+        #[cfg(feature = "numbers")]
+        app.register_i18n_component::<I18nNumber>()
+            .register_i18n_component::<I18nCurrency>()
+            .register_i18n_component::<I18nMeasure>()
+            .register_i18n_component::<I18nRelativeTime>()
+            .register_i18n_component::<I18nDuration>()
+            .add_systems(
+                Update,
+                (update_relative_times, update_durations).in_set(I18nSet::Live),
             );
+
+        #[cfg(feature = "datetime")]
+        app.register_i18n_component::<I18nDateTime>();
+
+        #[cfg(feature = "fluent")]
+        app.init_asset::<FluentAsset>()
+            .init_asset_loader::<FluentAssetLoader>()
+            .add_systems(Update, update_fluent_translations.before(finish_loading));
+
+        #[cfg(feature = "gettext")]
+        app.init_asset::<GettextAsset>()
+            .init_asset_loader::<GettextAssetLoader>()
+            .add_systems(Update, update_gettext_translations.before(finish_loading));
     }
 }
 
@@ -70,22 +132,250 @@ impl I18nComponentRegistration for App {
     }
 }
 
+/// Either a UI [Text] or a [Text2d]
+type AnyText = AnyOf<(&'static mut Text, &'static mut Text2d)>;
+
 /// Auto updates the translations for components that have the [I18nComponent] trait
 /// and have been registered with the Bevy [App] using the [register_i18n_component] method
-/// whenever the [I18n] resource changes
+/// whenever the [I18n] resource changes, e.g. when the locale is changed or a locale file is reloaded
 fn update_text_translations<T: I18nComponent + Component>(
-    font_manager: bevy::ecs::system::Res<FontManager>,
-    mut text_query: Query<(&mut Text, &mut TextFont, Option<&I18nFont>, &T)>,
+    i18n: Res<I18n>,
+    font_manager: Res<FontManager>,
+    mut commands: Commands,
+    mut text_query: Query<(Entity, AnyText, &mut TextFont, Option<&I18nFont>, &T)>,
 ) {
     bevy::log::debug!("Updating translations");
-    for (mut text, mut text_font, dyn_font, key) in text_query.iter_mut() {
-        text.0 = key.translate();
+    for (entity, (text, text_2d), mut text_font, dyn_font, key) in text_query.iter_mut() {
+        let (translated, missing) = key.translate_checked(&i18n);
+        send_missing_translation(&mut commands, missing, entity);
+        if let Some(mut text) = text {
+            text.0 = translated;
+        } else if let Some(mut text_2d) = text_2d {
+            text_2d.0 = translated;
+        }
         if let Some(dyn_font) = dyn_font {
-            text_font.font = font_manager.get(&dyn_font.0, key.locale());
+            text_font.font = font_manager.get(&dyn_font.0, &i18n.locale_chain(&key.locale()));
+        }
+    }
+}
+
+/// Moves the [I18nRelativeTime] components that have a refresh interval with the real time of the app,
+/// and re-renders them whenever their interval has elapsed
+#[cfg(feature = "numbers")]
+fn update_relative_times(
+    time: Res<Time<Real>>,
+    i18n: Res<I18n>,
+    mut query: Query<(AnyText, &mut I18nRelativeTime)>,
+) {
+    for ((text, text_2d), mut relative_time) in query.iter_mut() {
+        // The time moves every frame, only the re-rendered text is reported as changed
+        if !relative_time.bypass_change_detection().tick(time.delta()) {
+            continue;
+        }
+        let translated = relative_time.translate(&i18n);
+        if let Some(mut text) = text {
+            text.0 = translated;
+        } else if let Some(mut text_2d) = text_2d {
+            text_2d.0 = translated;
+        }
+    }
+}
+
+/// Moves the [I18nDuration] components that have a clock with the virtual time of the app,
+/// and re-renders the durations whose shown value changed, including the ones set by game code
+#[cfg(feature = "numbers")]
+fn update_durations(
+    time: Res<Time<Virtual>>,
+    i18n: Res<I18n>,
+    mut query: Query<(AnyText, &mut I18nDuration)>,
+) {
+    for ((text, text_2d), mut duration) in query.iter_mut() {
+        let duration = duration.bypass_change_detection();
+        duration.tick(time.delta());
+        if !duration.needs_render() {
+            continue;
+        }
+        let translated = duration.translate(&i18n);
+        if let Some(mut text) = text {
+            text.0 = translated;
+        } else if let Some(mut text_2d) = text_2d {
+            text_2d.0 = translated;
+        }
+    }
+}
+
+/// Loads the locale files specified in the [TRANSLATION_FILES] constant that's generated by the build script
+fn load_translations(
+    mut translation_manager: ResMut<TranslationManager>,
+    asset_server: Res<AssetServer>,
+) {
+    for path in TRANSLATION_FILES.iter() {
+        bevy::log::debug!("Loading locale file: {}", path);
+        match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            #[cfg(feature = "fluent")]
+            Some("ftl") => {
+                let handle: Handle<FluentAsset> = asset_server.load(*path);
+                translation_manager.fluent_handles.push(handle);
+            }
+            #[cfg(not(feature = "fluent"))]
+            Some("ftl") => {
+                bevy::log::warn!("Skipping {}, enable the `fluent` feature to load it", path);
+            }
+            #[cfg(feature = "gettext")]
+            Some("po" | "mo") => {
+                let handle: Handle<GettextAsset> = asset_server.load(*path);
+                translation_manager.gettext_handles.push(handle);
+            }
+            #[cfg(not(feature = "gettext"))]
+            Some("po" | "mo") => {
+                bevy::log::warn!("Skipping {}, enable the `gettext` feature to load it", path);
+            }
+            _ => {
+                let handle: Handle<TranslationAsset> = asset_server.load(*path);
+                translation_manager.handles.push(handle);
+            }
         }
     }
 }
 
+/// Marks the [I18n] resource as loaded once the translations of every locale file are applied,
+/// and negotiates its initial locale, so the requested locales are matched against all of the available locales
+fn finish_loading(
+    translation_manager: Res<TranslationManager>,
+    asset_server: Res<AssetServer>,
+    mut i18n: ResMut<I18n>,
+) {
+    let ids = translation_manager
+        .handles
+        .iter()
+        .map(|handle| handle.id().untyped());
+    #[cfg(feature = "fluent")]
+    let ids = ids.chain(
+        translation_manager
+            .fluent_handles
+            .iter()
+            .map(|handle| handle.id().untyped()),
+    );
+    #[cfg(feature = "gettext")]
+    let ids = ids.chain(
+        translation_manager
+            .gettext_handles
+            .iter()
+            .map(|handle| handle.id().untyped()),
+    );
+    let mut ids = ids;
+    // Failed files never load, they are skipped instead of blocking the negotiation
+    if ids.all(|id| {
+        translation_manager.applied.contains(&id)
+            || matches!(
+                asset_server.get_load_state(id),
+                Some(bevy::asset::LoadState::Failed(_))
+            )
+    }) {
+        i18n.finish_loading();
+    }
+}
+
+/// Rebuilds the translations of the [I18n] resource whenever a [TranslationAsset] is loaded, modified or removed,
+/// which in turn re-translates every registered component
+///
+/// With the `file_watcher` feature enabled, editing a locale file while the game is running
+/// triggers a [AssetEvent::Modified] event, so translators can iterate without rebuilding
+fn update_translations(
+    mut events: EventReader<AssetEvent<TranslationAsset>>,
+    translations: Res<Assets<TranslationAsset>>,
+    asset_server: Res<AssetServer>,
+    mut translation_manager: ResMut<TranslationManager>,
+    mut i18n: ResMut<I18n>,
+) {
+    let mut changed = false;
+    for event in events.read() {
+        match event {
+            AssetEvent::LoadedWithDependencies { id } => {
+                translation_manager.applied.insert(id.untyped());
+                changed = true;
+            }
+            AssetEvent::Removed { .. } => changed = true,
+            AssetEvent::Modified { id } => {
+                bevy::log::debug!("Locale file {} was modified, reloading translations", id);
+                changed = true;
+            }
+            _ => {}
+        }
+    }
+    if changed {
+        i18n.set_translations(sorted_by_path(&translations, &asset_server));
+    }
+}
+
+/// Returns the assets sorted by their asset path, so the first locale file with a key or message wins
+/// no matter the order the files were loaded in
+fn sorted_by_path<'a, A: Asset>(assets: &'a Assets<A>, asset_server: &AssetServer) -> Vec<&'a A> {
+    let mut assets: Vec<_> = assets
+        .iter()
+        .map(|(id, asset)| {
+            (
+                asset_server.get_path(id).map(|path| path.to_string()),
+                asset,
+            )
+        })
+        .collect();
+    assets.sort_by(|(a, _), (b, _)| a.cmp(b));
+    assets.into_iter().map(|(_, asset)| asset).collect()
+}
+
+/// Rebuilds the Fluent bundles of the [I18n] resource whenever a [FluentAsset] is loaded, modified or removed
+#[cfg(feature = "fluent")]
+fn update_fluent_translations(
+    mut events: EventReader<AssetEvent<FluentAsset>>,
+    fluent_assets: Res<Assets<FluentAsset>>,
+    asset_server: Res<AssetServer>,
+    mut translation_manager: ResMut<TranslationManager>,
+    mut i18n: ResMut<I18n>,
+) {
+    let mut changed = false;
+    for event in events.read() {
+        if let AssetEvent::LoadedWithDependencies { id } = event {
+            translation_manager.applied.insert(id.untyped());
+        }
+        if let AssetEvent::LoadedWithDependencies { .. }
+        | AssetEvent::Modified { .. }
+        | AssetEvent::Removed { .. } = event
+        {
+            changed = true;
+        }
+    }
+    if changed {
+        i18n.set_fluent_resources(sorted_by_path(&fluent_assets, &asset_server));
+    }
+}
+
+/// Rebuilds the gettext catalogs of the [I18n] resource whenever a [GettextAsset] is loaded, modified or removed
+#[cfg(feature = "gettext")]
+fn update_gettext_translations(
+    mut events: EventReader<AssetEvent<GettextAsset>>,
+    gettext_assets: Res<Assets<GettextAsset>>,
+    asset_server: Res<AssetServer>,
+    mut translation_manager: ResMut<TranslationManager>,
+    mut i18n: ResMut<I18n>,
+) {
+    let mut changed = false;
+    for event in events.read() {
+        if let AssetEvent::LoadedWithDependencies { id } = event {
+            translation_manager.applied.insert(id.untyped());
+        }
+        if let AssetEvent::LoadedWithDependencies { .. }
+        | AssetEvent::Modified { .. }
+        | AssetEvent::Removed { .. } = event
+        {
+            changed = true;
+        }
+    }
+    if changed {
+        i18n.set_gettext_catalogs(sorted_by_path(&gettext_assets, &asset_server));
+    }
+}
+
 /// Loads the dynamic fonts specified in the [FONT_FAMILIES] constant that's generated by the build script
 ///
 /// TODO: Make the loading state more controllable
@@ -95,8 +385,10 @@ fn load_dynamic_fonts(
 ) {
     for dyn_font in FONT_FAMILIES.iter() {
         bevy::log::debug!("Loading dynamic font family: {}", dyn_font.family);
-        let mut font_folder = FontFolder::default();
-        font_folder.fallback = asset_server.load(Path::new(dyn_font.path).join("fallback.ttf"));
+        let mut font_folder = FontFolder {
+            fallback: asset_server.load(Path::new(dyn_font.path).join("fallback.ttf")),
+            ..Default::default()
+        };
         for font in dyn_font.locales.iter() {
             bevy::log::debug!("Loading font: {}", font);
             let locale = font.split('.').next().expect("Locale is required");
@@ -126,3 +418,181 @@ fn monitor_font_loading(
     commands.remove_resource::<FontsLoading>();
     bevy::log::debug!("All fonts loaded");
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{asset::AssetPlugin, MinimalPlugins};
+    #[cfg(feature = "numbers")]
+    use bevy::{ecs::resource::Resource, time::TimeUpdateStrategy};
+
+    use super::*;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Font>()
+            .add_plugins(I18nPlugin);
+        app
+    }
+
+    /// Runs the app until the locale files are loaded, the assets are loaded on other threads
+    fn load(app: &mut App) {
+        for _ in 0..10_000 {
+            if app.world().resource::<I18n>().is_loaded() {
+                return;
+            }
+            app.update();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        panic!("The locale files were never loaded");
+    }
+
+    #[test]
+    fn locale_is_negotiated_once_every_locale_file_is_applied() {
+        let mut app = app();
+        app.world_mut()
+            .resource_mut::<I18n>()
+            .set_requested_locales(["fr-CA", "en"]);
+        load(&mut app);
+
+        let world = app.world();
+        let i18n = world.resource::<I18n>();
+        let assets = world.resource::<Assets<TranslationAsset>>();
+        let handles = &world.resource::<TranslationManager>().handles;
+        assert!(!handles.is_empty());
+        for handle in handles {
+            let asset = assets.get(handle).unwrap();
+            for (locale, translations) in asset.locales.iter() {
+                assert!(i18n.locales().contains(locale), "{}", locale);
+                for key in translations.keys() {
+                    assert!(
+                        i18n.exact_translation(locale, key).is_some(),
+                        "{} {}",
+                        locale,
+                        key
+                    );
+                }
+            }
+        }
+        // `fr` is only found in `v2_example.yml`
+        assert_eq!(i18n.current(), "fr");
+    }
+
+    #[test]
+    fn locale_set_by_the_game_is_not_negotiated() {
+        let mut app = app();
+        let mut i18n = app.world_mut().resource_mut::<I18n>();
+        i18n.set_requested_locales(["fr-CA"]);
+        i18n.set_locale("ja");
+        load(&mut app);
+        assert_eq!(app.world().resource::<I18n>().current(), "ja");
+    }
+
+    #[test]
+    fn modified_locale_files_retranslate_the_texts() {
+        let mut app = app();
+        load(&mut app);
+        let entity = app
+            .world_mut()
+            .spawn(I18nText::new("hello").with_locale("en"))
+            .id();
+        app.update();
+        assert_eq!(app.world().get::<Text>(entity).unwrap().0, "Hello World");
+
+        let world = app.world_mut();
+        let handle = world
+            .resource::<TranslationManager>()
+            .handles
+            .iter()
+            .find(|handle| {
+                world.resource::<AssetServer>().get_path(handle.id())
+                    == Some("locales/en.json".into())
+            })
+            .unwrap()
+            .clone();
+        world
+            .resource_mut::<Assets<TranslationAsset>>()
+            .get_mut(&handle)
+            .unwrap()
+            .locales
+            .get_mut("en")
+            .unwrap()
+            .insert("hello".to_string(), "Hello again".to_string());
+        // The `Modified` event is sent at the end of the frame
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(app.world().get::<Text>(entity).unwrap().0, "Hello again");
+    }
+
+    #[cfg(feature = "numbers")]
+    #[derive(Resource)]
+    struct Remaining(Duration);
+
+    #[cfg(feature = "numbers")]
+    fn set_durations(remaining: Res<Remaining>, mut query: Query<&mut I18nDuration>) {
+        for mut duration in query.iter_mut() {
+            duration.set_duration(remaining.0);
+        }
+    }
+
+    #[cfg(feature = "numbers")]
+    #[test]
+    fn durations_set_before_the_live_set_are_shown_the_same_frame() {
+        let mut app = app();
+        app.insert_resource(Remaining(Duration::from_secs(30)))
+            .add_systems(Update, set_durations.before(I18nSet::Live));
+        let entity = app
+            .world_mut()
+            .spawn(I18nDuration::new(Duration::from_secs(30)))
+            .id();
+        app.update();
+        assert_eq!(app.world().get::<Text>(entity).unwrap().0, "0:30");
+
+        for seconds in [29, 28, 3933] {
+            app.world_mut().resource_mut::<Remaining>().0 = Duration::from_secs(seconds);
+            app.update();
+            let expected = I18nDuration::new(Duration::from_secs(seconds))
+                .format(app.world().resource::<I18n>(), "en");
+            assert_eq!(app.world().get::<Text>(entity).unwrap().0, expected);
+        }
+    }
+
+    #[cfg(feature = "numbers")]
+    #[test]
+    fn relative_times_are_rendered_again_at_every_refresh() {
+        let mut app = app();
+        // Applying the locale files renders every text again, the frame after they are loaded
+        load(&mut app);
+        app.update();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(20)));
+        let entity = app
+            .world_mut()
+            .spawn(
+                I18nRelativeTime::new(0)
+                    .with_refresh(Duration::from_secs(60))
+                    .with_locale("en"),
+            )
+            .id();
+        let mut texts = Vec::new();
+        for _ in 0..7 {
+            app.update();
+            texts.push(app.world().get::<Text>(entity).unwrap().0.clone());
+        }
+        // The text only changes when the interval has elapsed
+        assert_eq!(
+            texts,
+            [
+                "in 0 seconds",
+                "in 0 seconds",
+                "1 minute ago",
+                "1 minute ago",
+                "1 minute ago",
+                "2 minutes ago",
+                "2 minutes ago",
+            ]
+        );
+    }
+}
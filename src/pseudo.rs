@@ -0,0 +1,151 @@
+use bevy::reflect::Reflect;
+
+/// Source locale of the pseudo-locales when the [crate::resources::I18n] resource has no default locale
+pub(crate) const PSEUDO_SOURCE_LOCALE: &str = "en";
+
+const PLAIN: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const ACCENTED: &str = "åƀçðéƒĝĥîĵķļɱñöþǫŕšţûṽŵẋýžÅƁÇÐÉƑĜĤÎĴĶĻṀÑÖÞǪŔŠŢÛṼŴẊÝŽ";
+
+/// Words appended to expand the pseudo-localized text
+const EXPANSION: &str = " one two three four five six seven eight nine ten";
+
+/// Pseudo-locales generated on the fly from the translations of the default locale,
+/// to catch hard-coded strings and layouts that break with longer or right-to-left text
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PseudoLocale {
+    /// `en-XA`, accented and expanded, `[Ĥéļļö ŵöŕļð one two]`
+    Accented,
+    /// `ar-XB`, mirrored with right-to-left overrides
+    Bidi,
+}
+
+impl PseudoLocale {
+    /// Returns the pseudo-locale of the locale, `None` if it is a regular locale
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        [Self::Accented, Self::Bidi]
+            .into_iter()
+            .find(|pseudo| pseudo.locale().eq_ignore_ascii_case(locale))
+    }
+
+    /// Returns the locale of the pseudo-locale, e.g. `en-XA`
+    pub fn locale(&self) -> &'static str {
+        match self {
+            Self::Accented => "en-XA",
+            Self::Bidi => "ar-XB",
+        }
+    }
+
+    /// Pseudo-localizes the translation, the `%{placeholders}` are kept as is
+    pub(crate) fn apply(&self, translation: &str, options: &PseudoLocaleOptions) -> String {
+        let mut result = String::new();
+        let mut length = 0;
+        let mut rest = translation;
+        while !rest.is_empty() {
+            let (text, placeholder) = match rest.find("%{") {
+                Some(start) => {
+                    let end = rest[start..]
+                        .find('}')
+                        .map_or(rest.len(), |end| start + end + 1);
+                    (&rest[..start], &rest[start..end])
+                }
+                None => (rest, ""),
+            };
+            length += text.chars().count();
+            result.push_str(&self.transform(text));
+            result.push_str(placeholder);
+            rest = &rest[text.len() + placeholder.len()..];
+        }
+
+        let expansion = (length as f32 * options.expansion).round() as usize;
+        let expansion: String = EXPANSION.chars().cycle().take(expansion).collect();
+        result.push_str(&self.transform(&expansion));
+        if options.brackets {
+            result = format!("[{}]", result);
+        }
+        match self {
+            Self::Accented => result,
+            // Right-to-left marks keep the brackets and punctuation on the mirrored side
+            Self::Bidi => format!("\u{200f}{}\u{200f}", result),
+        }
+    }
+
+    fn transform(&self, text: &str) -> String {
+        if text.is_empty() {
+            return String::new();
+        }
+        match self {
+            Self::Accented => text
+                .chars()
+                .map(|c| {
+                    PLAIN
+                        .chars()
+                        .position(|plain| plain == c)
+                        .and_then(|index| ACCENTED.chars().nth(index))
+                        .unwrap_or(c)
+                })
+                .collect(),
+            Self::Bidi => format!("\u{202e}{}\u{202c}", text),
+        }
+    }
+}
+
+/// Options of the pseudo-localized translations
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct PseudoLocaleOptions {
+    /// Length added to the translations, as a fraction of their length, `0.3` by default
+    pub expansion: f32,
+    /// Whether the translations are wrapped in brackets, so truncated text is easy to spot
+    pub brackets: bool,
+}
+
+impl Default for PseudoLocaleOptions {
+    fn default() -> Self {
+        Self {
+            expansion: 0.3,
+            brackets: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pseudo_locales() {
+        assert_eq!(
+            PseudoLocale::from_locale("en-XA"),
+            Some(PseudoLocale::Accented)
+        );
+        assert_eq!(PseudoLocale::from_locale("AR-xb"), Some(PseudoLocale::Bidi));
+        assert_eq!(PseudoLocale::from_locale("en"), None);
+        assert_eq!(PseudoLocale::Bidi.locale(), "ar-XB");
+    }
+
+    #[test]
+    fn accented_and_expanded() {
+        let options = PseudoLocaleOptions::default();
+        assert_eq!(
+            PseudoLocale::Accented.apply("Hello World", &options),
+            "[Ĥéļļö Ŵöŕļð öñ]"
+        );
+        let options = PseudoLocaleOptions {
+            expansion: 0.0,
+            brackets: false,
+        };
+        assert_eq!(PseudoLocale::Accented.apply("Hello", &options), "Ĥéļļö");
+    }
+
+    #[test]
+    fn placeholders_are_kept() {
+        let options = PseudoLocaleOptions::default();
+        assert_eq!(
+            PseudoLocale::Accented.apply("Hello %{name}!", &options),
+            "[Ĥéļļö %{name}! ö]"
+        );
+        assert_eq!(
+            PseudoLocale::Bidi.apply("Hi %{name}", &options),
+            "\u{200f}[\u{202e}Hi \u{202c}%{name}\u{202e} \u{202c}]\u{200f}"
+        );
+    }
+}
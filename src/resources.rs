@@ -1,14 +1,36 @@
 use bevy::{
-    asset::Handle,
+    asset::{Handle, UntypedAssetId},
     ecs::{reflect::ReflectResource, resource::Resource},
-    platform::collections::HashMap,
+    platform::collections::{HashMap, HashSet},
     reflect::Reflect,
     text::Font,
 };
 use icu_locid::Locale;
+use icu_locid_transform::LocaleExpander;
+
+#[cfg(feature = "fluent")]
+use crate::assets::{FluentAsset, FluentBundles};
+#[cfg(feature = "gettext")]
+use crate::assets::{GettextAsset, GettextCatalogs};
+use crate::{assets::TranslationAsset, pseudo::PseudoLocaleOptions, storage::LocaleStorage};
 
 /// Resource for managing the current locale and getting the available locales
 ///
+/// Missing translations fall back through the locale chain, `pt-BR` -> `pt`,
+/// which can be configured per locale with [I18n::set_fallbacks] and ends with the [I18n::set_default_locale] locale.
+/// The same chain picks the dynamic fonts and the locale used to format numbers and dates
+///
+/// The pseudo-locales `en-XA` and `ar-XB` are generated from the translations of the default locale,
+/// see [crate::pseudo::PseudoLocale]
+///
+/// The initial locale is negotiated once every locale file is loaded,
+/// matching the preferred locales of the user against the available locales,
+/// see [I18n::set_requested_locales], unless a locale saved by a [LocaleStorage] is restored
+///
+/// Also holds the translations of every loaded [TranslationAsset]
+/// (and `FluentAsset`/`GettextAsset` with the `fluent`/`gettext` features),
+/// the available locales are the ones found in those files
+///
 /// # Example
 /// ```
 /// use bevy::prelude::*;
@@ -17,23 +39,73 @@ use icu_locid::Locale;
 /// fn update_locale(mut i18n_res: ResMut<I18n>) {
 ///     i18n_res.set_locale("en");
 /// }
+///
+/// // `pt-BR` -> `pt` -> `es` -> `en`
+/// fn setup_fallbacks(mut i18n_res: ResMut<I18n>) {
+///     i18n_res.set_fallbacks("pt", ["es"]);
+///     i18n_res.set_default_locale("en");
+/// }
+/// ```
 #[derive(Debug, Resource, Reflect)]
 #[reflect(Resource)]
 pub struct I18n {
     locales: Vec<String>,
     current: String,
+    translations: HashMap<String, HashMap<String, String>>,
+    fallbacks: HashMap<String, Vec<String>>,
+    default_locale: Option<String>,
+    requested_locales: Vec<String>,
+    negotiate: bool,
+    loaded: bool,
+    #[reflect(ignore)]
+    storage: Option<StorageHandle>,
+    missing_policy: MissingTranslationPolicy,
+    pseudo_options: PseudoLocaleOptions,
+    #[cfg(feature = "fluent")]
+    #[reflect(ignore)]
+    fluent: FluentBundles,
+    #[cfg(feature = "gettext")]
+    #[reflect(ignore)]
+    gettext: GettextCatalogs,
 }
 
 impl I18n {
+    /// Set the current locale, it is saved by the [LocaleStorage] if there is one
     pub fn set_locale(&mut self, locale: impl Into<String>) {
-        let next_locale: String = locale.into();
+        if !self.switch_locale(locale.into()) {
+            return;
+        }
+        // A locale picked by the game wins over the negotiated one
+        self.negotiate = false;
+        if let Some(StorageHandle(storage)) = self.storage.as_mut() {
+            storage.save(&self.current);
+        }
+    }
+
+    /// Set the storage that saves the locale whenever it is set with [I18n::set_locale],
+    /// and restore the locale it saved in a previous session, if any
+    ///
+    /// The restored locale takes precedence over the negotiated one when the storage is set
+    /// before the locale files are loaded, e.g. in a `Startup` system
+    pub fn set_locale_storage(&mut self, storage: impl LocaleStorage) {
+        if let Some(locale) = storage.load() {
+            bevy::log::debug!("Restoring the saved locale {}", locale);
+            if self.switch_locale(locale) {
+                self.negotiate = false;
+            }
+        }
+        self.storage = Some(StorageHandle(Box::new(storage)));
+    }
+
+    fn switch_locale(&mut self, next_locale: String) -> bool {
         if let Err(err) = next_locale.parse::<Locale>() {
             bevy::log::error!("Invalid locale: {}", err);
-            return;
+            return false;
         }
         rust_i18n::set_locale(&next_locale);
         bevy::log::debug!("Locale changed from {} to {}", self.current, next_locale);
         self.current = next_locale;
+        true
     }
 
     pub fn current(&self) -> &str {
@@ -43,29 +115,358 @@ impl I18n {
     pub fn locales(&self) -> &[String] {
         &self.locales
     }
+
+    /// Set the locales to fall back to, in order, when a translation is missing for the provided locale,
+    /// replacing the parent locale, e.g. `zh-TW` -> `zh-Hant` instead of `zh-TW` -> `zh`
+    ///
+    /// Each fallback locale keeps falling back through its own chain
+    pub fn set_fallbacks(
+        &mut self,
+        locale: impl Into<String>,
+        fallbacks: impl IntoIterator<Item = impl Into<String>>,
+    ) {
+        let locale: String = locale.into();
+        let fallbacks: Vec<String> = fallbacks.into_iter().map(Into::into).collect();
+        if let Some(err) = fallbacks
+            .iter()
+            .chain([&locale])
+            .find_map(|locale| locale.parse::<Locale>().err())
+        {
+            bevy::log::error!("Invalid locale: {}", err);
+            return;
+        }
+        bevy::log::debug!("Fallbacks of {} set to {:?}", locale, fallbacks);
+        self.fallbacks.insert(locale, fallbacks);
+    }
+
+    /// Set the locale that every locale chain ends with, used when no other locale has the translation
+    pub fn set_default_locale(&mut self, locale: impl Into<String>) {
+        let locale: String = locale.into();
+        if let Err(err) = locale.parse::<Locale>() {
+            bevy::log::error!("Invalid locale: {}", err);
+            return;
+        }
+        bevy::log::debug!("Default locale set to {}", locale);
+        self.default_locale = Some(locale);
+    }
+
+    pub fn default_locale(&self) -> Option<&str> {
+        self.default_locale.as_deref()
+    }
+
+    /// Set the preferred locales of the user, in order, that the initial locale is negotiated from
+    ///
+    /// With the `system_locale` feature, they are detected from the operating system,
+    /// e.g. `LANGUAGE`, `LC_ALL`, `LC_MESSAGES` and `LANG` on Linux or `navigator.languages` on the web.
+    /// This replaces them, e.g. with the language of a Steam or console account
+    ///
+    /// Only used if set before the locale files are loaded, e.g. in a `Startup` system,
+    /// later on the locale can be set with [I18n::match_locale] and [I18n::set_locale]
+    pub fn set_requested_locales(&mut self, locales: impl IntoIterator<Item = impl Into<String>>) {
+        self.requested_locales = locales.into_iter().map(Into::into).collect();
+        bevy::log::debug!("Requested locales set to {:?}", self.requested_locales);
+    }
+
+    pub fn requested_locales(&self) -> &[String] {
+        &self.requested_locales
+    }
+
+    /// Set what is shown when a text has no translation in the language of its locale
+    pub fn set_missing_policy(&mut self, policy: MissingTranslationPolicy) {
+        self.missing_policy = policy;
+    }
+
+    pub fn missing_policy(&self) -> MissingTranslationPolicy {
+        self.missing_policy
+    }
+
+    /// Set the options of the pseudo-locales, `en-XA` and `ar-XB`, see [crate::pseudo::PseudoLocale]
+    pub fn set_pseudo_options(&mut self, options: PseudoLocaleOptions) {
+        self.pseudo_options = options;
+    }
+
+    pub fn pseudo_options(&self) -> &PseudoLocaleOptions {
+        &self.pseudo_options
+    }
+
+    /// Returns the available locale that best matches the requested locales, in order of preference
+    ///
+    /// Each requested locale is matched exactly first, then with its parent locales, `pt-BR` -> `pt`,
+    /// then with a locale of the same language and script, preferring the same region,
+    /// `en-GB` -> `en-US`, `zh-HK` -> `zh-TW`
+    pub fn match_locale(&self, requested: &[impl AsRef<str>]) -> Option<&str> {
+        let expander = LocaleExpander::new();
+        let available: Vec<(&String, Locale)> = self
+            .locales
+            .iter()
+            .filter_map(|locale| {
+                let mut maximized = locale.parse::<Locale>().ok()?;
+                expander.maximize(&mut maximized);
+                Some((locale, maximized))
+            })
+            .collect();
+
+        for requested in requested {
+            let Ok(mut requested) = requested.as_ref().parse::<Locale>() else {
+                continue;
+            };
+            let requested_string = requested.to_string();
+            let mut candidate = requested_string.as_str();
+            loop {
+                if let Some(locale) = self
+                    .locales
+                    .iter()
+                    .find(|locale| locale.eq_ignore_ascii_case(candidate))
+                {
+                    return Some(locale);
+                }
+                match candidate.rfind('-') {
+                    Some(index) => candidate = &candidate[..index],
+                    None => break,
+                }
+            }
+
+            expander.maximize(&mut requested);
+            let mut same_script = available.iter().filter(|(_, locale)| {
+                locale.id.language == requested.id.language
+                    && locale.id.script == requested.id.script
+            });
+            if let Some((locale, _)) = same_script
+                .clone()
+                .find(|(_, locale)| locale.id.region == requested.id.region)
+                .or_else(|| same_script.next())
+            {
+                return Some(locale);
+            }
+        }
+        None
+    }
+
+    /// Returns the translation of the key for the most specific locale that has it
+    ///
+    /// `en-US` -> `en`
+    pub fn translation(&self, locale: &str, key: &str) -> Option<&str> {
+        self.locale_chain(locale)
+            .iter()
+            .find_map(|locale| self.exact_translation(locale, key))
+    }
+
+    /// Returns the locales to look into, in order, when translating for the provided locale
+    ///
+    /// `en-US` -> `en`, or the configured fallbacks of a locale instead of its parent,
+    /// then the default locale
+    pub fn locale_chain(&self, locale: &str) -> Vec<String> {
+        let mut chain = vec![];
+        self.push_locale_chain(&mut chain, locale);
+        if let Some(default_locale) = &self.default_locale {
+            self.push_locale_chain(&mut chain, default_locale);
+        }
+        chain
+    }
+
+    fn push_locale_chain(&self, chain: &mut Vec<String>, locale: &str) {
+        if locale.is_empty() || chain.iter().any(|l| l == locale) {
+            return;
+        }
+        chain.push(locale.to_string());
+        if let Some(fallbacks) = self.fallbacks.get(locale) {
+            for fallback in fallbacks {
+                self.push_locale_chain(chain, fallback);
+            }
+        } else if let Some(index) = locale.rfind('-') {
+            self.push_locale_chain(chain, &locale[..index]);
+        }
+    }
+
+    /// Returns the translation of the key for this exact locale, without any fallback
+    pub(crate) fn exact_translation(&self, locale: &str, key: &str) -> Option<&str> {
+        self.translations
+            .get(locale)
+            .and_then(|translations| translations.get(key))
+            .map(String::as_str)
+    }
+
+    #[cfg(feature = "fluent")]
+    pub(crate) fn fluent(&self) -> &FluentBundles {
+        &self.fluent
+    }
+
+    #[cfg(feature = "gettext")]
+    pub(crate) fn gettext(&self) -> &GettextCatalogs {
+        &self.gettext
+    }
+
+    /// Replaces the translations with the ones from the provided assets and updates the available locales
+    ///
+    /// The first asset with a key wins, so the assets should be ordered, e.g. by their asset path
+    pub(crate) fn set_translations<'a>(
+        &mut self,
+        assets: impl IntoIterator<Item = &'a TranslationAsset>,
+    ) {
+        self.translations.clear();
+        for asset in assets {
+            for (locale, translations) in asset.locales.iter() {
+                let merged = self.translations.entry(locale.clone()).or_default();
+                for (key, translation) in translations.iter() {
+                    merged
+                        .entry(key.clone())
+                        .or_insert_with(|| translation.clone());
+                }
+            }
+        }
+        self.update_locales();
+    }
+
+    /// Replaces the Fluent bundles with the ones built from the provided assets and updates the available locales
+    ///
+    /// The first asset with a message wins, like with [I18n::set_translations]
+    #[cfg(feature = "fluent")]
+    pub(crate) fn set_fluent_resources<'a>(
+        &mut self,
+        assets: impl IntoIterator<Item = &'a FluentAsset>,
+    ) {
+        self.fluent = FluentBundles::new(assets);
+        self.update_locales();
+    }
+
+    /// Replaces the gettext catalogs with the ones from the provided assets and updates the available locales
+    ///
+    /// The first asset with a message wins, like with [I18n::set_translations]
+    #[cfg(feature = "gettext")]
+    pub(crate) fn set_gettext_catalogs<'a>(
+        &mut self,
+        assets: impl IntoIterator<Item = &'a GettextAsset>,
+    ) {
+        self.gettext = GettextCatalogs::new(assets);
+        self.update_locales();
+    }
+
+    fn update_locales(&mut self) {
+        let locales = self.translations.keys();
+        #[cfg(feature = "fluent")]
+        let locales = locales.chain(self.fluent.locales());
+        #[cfg(feature = "gettext")]
+        let locales = locales.chain(self.gettext.locales());
+        let mut locales: Vec<String> = locales.cloned().collect();
+        locales.sort();
+        locales.dedup();
+        bevy::log::debug!("Translations loaded for locales: {:?}", locales);
+        self.locales = locales;
+    }
+
+    /// Returns `true` once every locale file found by the build script is loaded
+    pub fn is_loaded(&self) -> bool {
+        self.loaded
+    }
+
+    /// Marks the locale files as loaded and negotiates the initial locale, unless it was set by the game
+    pub(crate) fn finish_loading(&mut self) {
+        if self.negotiate {
+            self.negotiate_locale();
+        }
+        self.loaded = true;
+    }
+
+    /// Sets the locale that best matches the requested locales, or the default locale if none matches
+    fn negotiate_locale(&mut self) {
+        let locale = self
+            .match_locale(&self.requested_locales)
+            .or(self.default_locale.as_deref())
+            .map(ToString::to_string);
+        bevy::log::debug!(
+            "Negotiated locale {:?} from the requested locales {:?}",
+            locale,
+            self.requested_locales
+        );
+        // The negotiated locale is not saved, so it follows the system locales of the next sessions
+        if let Some(locale) = locale {
+            self.switch_locale(locale);
+        }
+        self.negotiate = false;
+    }
 }
 
 impl Default for I18n {
     fn default() -> Self {
         Self {
             current: rust_i18n::locale().to_string(),
-            locales: rust_i18n::available_locales!()
-                .into_iter()
-                .map(|s| s.into())
-                .collect(),
+            locales: vec![],
+            translations: HashMap::default(),
+            fallbacks: HashMap::default(),
+            default_locale: None,
+            requested_locales: system_locales(),
+            negotiate: true,
+            loaded: false,
+            storage: None,
+            missing_policy: MissingTranslationPolicy::default(),
+            pseudo_options: PseudoLocaleOptions::default(),
+            #[cfg(feature = "fluent")]
+            fluent: FluentBundles::default(),
+            #[cfg(feature = "gettext")]
+            gettext: GettextCatalogs::default(),
         }
     }
 }
 
+/// What is shown when a text has no translation in the language of its locale,
+/// which is always reported as a [crate::events::MissingTranslation]
+#[derive(Default, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingTranslationPolicy {
+    /// The translation of a fallback locale of the [I18n::locale_chain], or the key if none has it
+    #[default]
+    Fallback,
+    /// The key itself, `hello`
+    Key,
+    /// The key between visible markers, `⟦hello⟧`
+    Marker,
+    /// Panics in debug builds, like `Fallback` in release builds
+    Panic,
+}
+
+/// Keeps the [LocaleStorage] of the [I18n] resource, which is not `Debug`
+struct StorageHandle(Box<dyn LocaleStorage>);
+
+impl std::fmt::Debug for StorageHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("LocaleStorage")
+    }
+}
+
+/// Returns the preferred locales of the user from the operating system, in order
+#[cfg(feature = "system_locale")]
+fn system_locales() -> Vec<String> {
+    let locales: Vec<String> = sys_locale::get_locales().collect();
+    bevy::log::debug!("System locales: {:?}", locales);
+    locales
+}
+
+#[cfg(not(feature = "system_locale"))]
+fn system_locales() -> Vec<String> {
+    vec![]
+}
+
+/// Resource that keeps the locale files found by the build script loaded
+#[derive(Debug, Reflect, Default, Resource)]
+#[reflect(Resource)]
+pub(crate) struct TranslationManager {
+    /// Locale files whose translations were applied to the [I18n] resource,
+    /// the initial locale is only negotiated once all of them are, not as soon as they are loaded
+    #[reflect(ignore)]
+    pub(crate) applied: HashSet<UntypedAssetId>,
+    pub(crate) handles: Vec<Handle<TranslationAsset>>,
+    #[cfg(feature = "fluent")]
+    pub(crate) fluent_handles: Vec<Handle<FluentAsset>>,
+    #[cfg(feature = "gettext")]
+    pub(crate) gettext_handles: Vec<Handle<GettextAsset>>,
+}
+
 /// Internal struct for managing fonts for a specific font family.
 ///
-/// It attempts to find a specified font for the most specific locale.
-///
-/// If unsuccessful, it will split the locale at the last `-` and try again.
+/// It attempts to find a specified font for each locale of the [I18n::locale_chain], in order.
 ///
 /// `en-US` -> `en` -> `fallback`
 ///
-/// If still unsuccessful, it will return the fallback font.
+/// If unsuccessful, it will return the fallback font.
 #[derive(Debug, Default, Reflect)]
 pub(crate) struct FontFolder {
     pub(crate) fallback: Handle<Font>,
@@ -73,22 +474,14 @@ pub(crate) struct FontFolder {
 }
 
 impl FontFolder {
-    pub(crate) fn get(&self, locale: impl Into<String>) -> Handle<Font> {
-        let locale: String = locale.into();
-        let mut locale = locale.as_str();
-
-        bevy::log::debug!("Evaluating font for {} locale", locale);
-        while !locale.is_empty() {
+    pub(crate) fn get(&self, locale_chain: &[String]) -> Handle<Font> {
+        bevy::log::debug!("Evaluating font for {:?} locales", locale_chain);
+        for locale in locale_chain {
             if let Some(font) = self.fonts.get(locale) {
                 bevy::log::debug!("Font for {} locale found", locale);
                 return font.clone();
             }
-            if let Some(index) = locale.rfind('-') {
-                bevy::log::debug!("Font for {} locale was not found", locale);
-                locale = &locale[..index];
-            } else {
-                break;
-            }
+            bevy::log::debug!("Font for {} locale was not found", locale);
         }
 
         bevy::log::debug!("Returning the fallback font");
@@ -110,10 +503,10 @@ impl FontManager {
         self.fonts.insert(family, font_folder);
     }
 
-    pub(crate) fn get(&self, family: &str, locale: String) -> Handle<Font> {
+    pub(crate) fn get(&self, family: &str, locale_chain: &[String]) -> Handle<Font> {
         if let Some(folder) = self.fonts.get(family) {
             bevy::log::debug!("Found font family: {}", family);
-            folder.get(locale)
+            folder.get(locale_chain)
         } else {
             bevy::log::debug!("Font {} was not found, using default", family);
             Handle::<Font>::default()
@@ -125,3 +518,73 @@ impl FontManager {
 #[derive(Debug, Reflect, Default, Resource)]
 #[reflect(Resource)]
 pub(crate) struct FontsLoading;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(locale: &str, translations: &[(&str, &str)]) -> TranslationAsset {
+        let translations = translations
+            .iter()
+            .map(|(key, text)| (key.to_string(), text.to_string()))
+            .collect();
+        TranslationAsset {
+            locales: HashMap::from_iter([(locale.to_string(), translations)]),
+        }
+    }
+
+    #[test]
+    fn first_asset_with_a_key_wins() {
+        let first = asset("en", &[("hello", "Hello"), ("bye", "Bye")]);
+        let second = asset("en", &[("hello", "Hi"), ("thanks", "Thanks")]);
+        let mut i18n = I18n::default();
+        i18n.set_translations([&first, &second]);
+
+        assert_eq!(i18n.translation("en", "hello"), Some("Hello"));
+        assert_eq!(i18n.translation("en", "bye"), Some("Bye"));
+        assert_eq!(i18n.translation("en", "thanks"), Some("Thanks"));
+        assert_eq!(i18n.locales(), ["en"]);
+    }
+
+    #[test]
+    fn match_locale() {
+        let assets =
+            ["en-US", "pt", "zh-CN", "zh-TW", "fr"].map(|locale| asset(locale, &[("hello", "")]));
+        let mut i18n = I18n::default();
+        i18n.set_translations(&assets);
+
+        // Exact matches ignore the case
+        assert_eq!(i18n.match_locale(&["EN-us"]), Some("en-US"));
+        // Parent locales
+        assert_eq!(i18n.match_locale(&["pt-BR"]), Some("pt"));
+        // Same language and script, the same region first
+        assert_eq!(i18n.match_locale(&["en-GB"]), Some("en-US"));
+        assert_eq!(i18n.match_locale(&["zh-HK"]), Some("zh-TW"));
+        assert_eq!(i18n.match_locale(&["zh-SG"]), Some("zh-CN"));
+        // Requested locales in order of preference, invalid ones are skipped
+        assert_eq!(
+            i18n.match_locale(&["de", "not a locale", "fr-CA"]),
+            Some("fr")
+        );
+        assert_eq!(i18n.match_locale(&["ja", "ko"]), None);
+    }
+
+    #[test]
+    fn locale_chain() {
+        let mut i18n = I18n::default();
+        assert_eq!(i18n.locale_chain("en-US"), ["en-US", "en"]);
+
+        i18n.set_default_locale("en");
+        assert_eq!(i18n.locale_chain("pt-BR"), ["pt-BR", "pt", "en"]);
+        assert_eq!(i18n.locale_chain("en-US"), ["en-US", "en"]);
+
+        // Fallbacks replace the parent locale and keep falling back through their own chain
+        i18n.set_fallbacks("zh-TW", ["zh-Hant-HK", "zh-Hant"]);
+        assert_eq!(
+            i18n.locale_chain("zh-TW"),
+            ["zh-TW", "zh-Hant-HK", "zh-Hant", "zh", "en"]
+        );
+        i18n.set_fallbacks("pt", ["es"]);
+        assert_eq!(i18n.locale_chain("pt-BR"), ["pt-BR", "pt", "es", "en"]);
+    }
+}
@@ -0,0 +1,89 @@
+/// Storage for the locale selected by the player, so it is restored in the next sessions
+///
+/// Implement it to keep the locale in your own settings system,
+/// or use [LocaleFile] with the `persistence` feature
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_simple_i18n::prelude::*;
+///
+/// struct SettingsStorage;
+///
+/// impl LocaleStorage for SettingsStorage {
+///     fn load(&self) -> Option<String> {
+///         // Read the locale from the settings of the game
+///         None
+///     }
+///
+///     fn save(&mut self, locale: &str) {
+///         // Write the locale to the settings of the game
+///     }
+/// }
+///
+/// fn setup_storage(mut i18n: ResMut<I18n>) {
+///     i18n.set_locale_storage(SettingsStorage);
+/// }
+/// ```
+pub trait LocaleStorage: Send + Sync + 'static {
+    /// Returns the saved locale, `None` if no locale was saved yet
+    fn load(&self) -> Option<String>;
+
+    /// Saves the locale, called whenever the locale is set with [crate::resources::I18n::set_locale]
+    fn save(&mut self, locale: &str);
+}
+
+/// Keeps the locale in a file of the platform config directory,
+/// e.g. `~/.config/{app}/locale` on Linux or `%APPDATA%\{app}\locale` on Windows
+///
+/// Nothing is saved on platforms without a config directory, e.g. the web
+#[cfg(feature = "persistence")]
+#[derive(Debug, Clone)]
+pub struct LocaleFile {
+    path: Option<std::path::PathBuf>,
+}
+
+#[cfg(feature = "persistence")]
+impl LocaleFile {
+    /// Creates a new `LocaleFile` in the folder of the app in the platform config directory
+    pub fn new(app: impl AsRef<std::path::Path>) -> Self {
+        Self {
+            path: dirs::config_dir().map(|dir| dir.join(app).join("locale")),
+        }
+    }
+
+    /// Creates a new `LocaleFile` at the provided path
+    pub fn at(path: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+        }
+    }
+
+    /// Returns the path of the file, `None` if the platform has no config directory
+    pub fn path(&self) -> Option<&std::path::Path> {
+        self.path.as_deref()
+    }
+}
+
+#[cfg(feature = "persistence")]
+impl LocaleStorage for LocaleFile {
+    fn load(&self) -> Option<String> {
+        let locale = std::fs::read_to_string(self.path.as_ref()?).ok()?;
+        let locale = locale.trim();
+        (!locale.is_empty()).then(|| locale.to_string())
+    }
+
+    fn save(&mut self, locale: &str) {
+        let Some(path) = &self.path else {
+            bevy::log::debug!("No config directory to save the locale to");
+            return;
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(path, locale));
+        if let Err(err) = result {
+            bevy::log::warn!("Could not save the locale to {}: {}", path.display(), err);
+        }
+    }
+}